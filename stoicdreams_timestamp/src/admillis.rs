@@ -0,0 +1,51 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds since the start of AD 0, the base `DateTime` stores internally.
+///
+/// Wrapping the value keeps it from being mixed up with `UnixMillis`, which differ by the 1970 years between the two bases.
+/// The two types never convert implicitly, so use `to_unix_millis()` or `UnixMillis::to_ad_millis()` to change base.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+/// let ad_millis = datetime.to_ad_millis();
+/// assert_eq!(ad_millis.to_unix_millis(), UnixMillis(1_685_284_606_076));
+/// assert_eq!(DateTime::from_ad_millis(ad_millis), Ok(datetime));
+/// ```
+///
+/// An `AdMillis` cannot be passed where a `UnixMillis` is expected:
+/// ```compile_fail
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let unix_millis: UnixMillis = AdMillis(0);
+/// ```
+///
+/// Serde reads and writes the bare integer, so wrapping a field in `AdMillis` keeps its wire format unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AdMillis(pub DateTimeUnit);
+
+impl AdMillis {
+    /// Converts to milliseconds since the Unix epoch, saturating at `i64::MIN`.
+    pub fn to_unix_millis(self) -> UnixMillis {
+        UnixMillis(self.0.saturating_sub(EPOCH_START))
+    }
+}
+
+impl From<DateTime> for AdMillis {
+    fn from(datetime: DateTime) -> Self {
+        datetime.to_ad_millis()
+    }
+}
+
+/// Returns a `TimeError` when the value is before AD 0 or after `DateTime::MAX`, the same as `DateTime::from_ad_millis()`.
+impl TryFrom<AdMillis> for DateTime {
+    type Error = TimeError;
+
+    fn try_from(milliseconds: AdMillis) -> Result<Self, Self::Error> {
+        DateTime::from_ad_millis(milliseconds)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Calendar systems that a `DateTime` can be displayed in with `DateTime::calendar_date()` and `DateTime::format_in_calendar()`.
+///
+/// `DateTime` always uses the proleptic Gregorian calendar (`Gregorian`, the default), extending Gregorian rules to dates before
+/// the calendar was introduced in October 1582. Historical records from before then use the `Julian` calendar,
+/// which drifts from the Gregorian calendar by an extra day in most century years (10 days in 1582).
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let datetime = DateTime::new(1582, 10, 15, 0, 0, 0);
+/// assert_eq!(datetime.calendar_date(CalendarSystem::Gregorian), (1582, 10, 15));
+/// assert_eq!(datetime.calendar_date(CalendarSystem::Julian), (1582, 10, 5));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum CalendarSystem {
+    /// The proleptic Gregorian calendar used by `DateTime`.
+    #[default]
+    Gregorian,
+    /// The Julian calendar, in use in Europe before the Gregorian reform of 1582.
+    Julian,
+}
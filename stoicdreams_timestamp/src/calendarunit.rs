@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Units used when counting the difference between two `DateTime` values with `DateTime::count_between()`.
+///
+/// `Years` and `Months` use calendar logic, counting only whole calendar months that have passed.
+/// All other units count whole units of a fixed length.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let start = DateTime::new(2019, 3, 15, 10, 20, 30);
+/// let end = DateTime::new(2023, 5, 28, 14, 36, 46);
+/// assert_eq!(start.count_between(&end, CalendarUnit::Years), 4);
+/// assert_eq!(start.count_between(&end, CalendarUnit::Months), 50);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum CalendarUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+}
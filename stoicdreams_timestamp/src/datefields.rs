@@ -0,0 +1,41 @@
+use crate::prelude::*;
+
+/// The calendar and clock fields of a `DateTime`, decomposed together in a single pass.
+///
+/// Expected usage is to use `DateTime::fields()` to create a new DateFields struct,
+/// which is cheaper than calling each `DateTime::get_*` method when several fields are needed.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let fields = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076).fields();
+/// assert_eq!(fields.year, 2023);
+/// assert_eq!(fields.month, 5);
+/// assert_eq!(fields.day, 28);
+/// assert_eq!(fields.hour, 14);
+/// assert_eq!(fields.millisecond, 76);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateFields {
+    pub year: u16,
+    /// Month of the year, where 1 is January.
+    pub month: u8,
+    /// Day of the month, starting at 1.
+    pub day: u16,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub millisecond: u16,
+    /// Day of the week, where 0 is Sunday and 6 is Saturday.
+    pub day_of_week: u8,
+    /// Ordinal day of the year, where 1 is January 1st.
+    pub day_of_year: u16,
+}
+
+impl DateFields {
+    /// Returns `day_of_week` as a `Weekday`.
+    pub fn weekday(&self) -> Weekday {
+        Weekday::from_day_of_week(self.day_of_week).unwrap_or(Weekday::Sunday)
+    }
+}
@@ -1,12 +1,35 @@
+use crate::parser::Parser;
 use crate::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+/// The layout used by `DateTime::format()`.
+const DEFAULT_LAYOUT: &str = "%Y-%m-%d %H:%M:%S.%f";
 
 pub type DateTimeUnit = i64;
 
+/// Milliseconds from the start of AD 0 until 0001-01-01, the epoch of .NET `DateTime.Ticks`.
+/// Year 0 is counted as a common year of 365 days (see `days_in_month()`).
+const DOTNET_EPOCH_START: DateTimeUnit = 365 * 24 * 60 * 60 * 1000;
+
+/// Milliseconds from the start of AD 0 until 1899-12-30, the epoch of Excel serial dates.
+const EXCEL_EPOCH_START: DateTimeUnit = EPOCH_START - 2_209_161_600_000;
+
+/// Milliseconds from the start of AD 0 until 1601-01-01, the epoch of Windows `FILETIME` values.
+const FILETIME_EPOCH_START: DateTimeUnit = EPOCH_START - 11_644_473_600_000;
+
 /// A date and time struct that can be used to represent a time in milliseconds.
 /// Expected usage is to use one of the following methods to create a new DateTime struct:
 /// - `DateTime::now()`
+/// - `DateTime::epoch()`
 /// - `DateTime::new(year, month, day, hour, minutes, seconds)`
+/// - `DateTime::from_ym(year, Month::May, day)`
 /// - `DateTime::from_unix_epoch_milliseconds(milliseconds)`
 ///
 /// Example:
@@ -17,28 +40,286 @@ pub type DateTimeUnit = i64;
 /// let datetime_display = datetime.format();
 /// println!("{}", datetime_display);
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+///
+/// A `DateTime` always serializes as a struct holding milliseconds since the start of AD 0 (`{"milliseconds": ...}`).
+///
+/// Human readable formats such as JSON also deserialize a bare number or numeric string, which is treated as
+/// milliseconds since the Unix epoch (Javascript's `Date.now()`), so the two forms use different bases.
+/// Numeric strings are accepted because Javascript clients often send large integers as strings to avoid losing precision.
+/// Compact formats such as bincode or postcard only deserialize the struct form, as they cannot tell the forms apart.
+/// In every form, values before AD 0 or after `DateTime::MAX` are rejected with a deserialization error.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let from_number: DateTime = serde_json::from_str("1685284606076").unwrap();
+/// let from_string: DateTime = serde_json::from_str("\"1685284606076\"").unwrap();
+/// assert_eq!(from_number, from_string);
+/// assert_eq!(from_number.format(), "2023-05-28 14:36:46.076");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct DateTime {
     /// Milliseconds since the start of AD 0.
     milliseconds: DateTimeUnit,
 }
 
+/// The default `DateTime` is the Unix epoch (Midnight of Jan 1st, 1970), so that `#[derive(Default)]` structs are deterministic.
+/// Use `DateTime::now()` when the current time is wanted.
 impl Default for DateTime {
     fn default() -> Self {
-        Self::now()
+        Self::epoch()
+    }
+}
+
+/// Converts milliseconds since the start of AD 0 (the same base as `DateTime::to_milliseconds()`).
+/// Negative values are before AD 0, and values after `DateTime::MAX` cannot be decomposed, so both return a `TimeError`.
+impl TryFrom<i64> for DateTime {
+    type Error = TimeError;
+
+    fn try_from(milliseconds: i64) -> Result<Self, Self::Error> {
+        if !(0..=Self::MAX.milliseconds).contains(&milliseconds) {
+            return Err(TimeError::OutOfRange {
+                field: "milliseconds",
+                value: milliseconds as i128,
+            });
+        }
+        Ok(Self { milliseconds })
+    }
+}
+
+/// Converts milliseconds since the start of AD 0 (the same base as `DateTime::to_milliseconds()`).
+/// Values after `DateTime::MAX` return a `TimeError`.
+impl TryFrom<u64> for DateTime {
+    type Error = TimeError;
+
+    fn try_from(milliseconds: u64) -> Result<Self, Self::Error> {
+        let milliseconds =
+            DateTimeUnit::try_from(milliseconds).map_err(|_| TimeError::OutOfRange {
+                field: "milliseconds",
+                value: milliseconds as i128,
+            })?;
+        Self::try_from(milliseconds)
+    }
+}
+
+/// Moves a `DateTime` forward by a `Time`, saturating at `DateTime::MAX`, the same as `DateTime::add()`.
+impl std::ops::Add<Time> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, time: Time) -> DateTime {
+        DateTime::add(&self, &time)
+    }
+}
+
+/// Moves a `DateTime` back by a `Time`, saturating at AD 0, the same as `DateTime::subtract()`.
+impl std::ops::Sub<Time> for DateTime {
+    type Output = DateTime;
+
+    fn sub(self, time: Time) -> DateTime {
+        self.subtract(&time)
+    }
+}
+
+/// Parses an RFC 3339 / ISO 8601 date and time, e.g. `"2023-05-28T14:36:46.076Z"` (see `DateTime::from_rfc3339()`).
+/// An offset such as `+02:00` is converted to UTC.
+impl FromStr for DateTime {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_rfc3339(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(DateTimeVisitor);
+        }
+        let value = DateTimeStruct::deserialize(deserializer)?;
+        DateTime::try_from(value.milliseconds).map_err(de::Error::custom)
+    }
+}
+
+/// The serialized struct form of a `DateTime`, used by formats that are not human readable.
+#[derive(Deserialize)]
+#[serde(rename = "DateTime")]
+struct DateTimeStruct {
+    milliseconds: DateTimeUnit,
+}
+
+/// Parses a milliseconds value that was sent as a string.
+fn parse_milliseconds_str<E: de::Error>(value: &str) -> Result<DateTimeUnit, E> {
+    value.trim().parse::<DateTimeUnit>().map_err(|_| {
+        E::invalid_value(
+            de::Unexpected::Str(value),
+            &"an integer number of milliseconds",
+        )
+    })
+}
+
+fn u64_to_milliseconds<E: de::Error>(value: u64) -> Result<DateTimeUnit, E> {
+    DateTimeUnit::try_from(value).map_err(|_| {
+        E::invalid_value(
+            de::Unexpected::Unsigned(value),
+            &"milliseconds within i64 range",
+        )
+    })
+}
+
+/// Visits the Unix epoch millisecond forms (number or numeric string) and the AD 0 struct form of a `DateTime`.
+pub(crate) struct DateTimeVisitor;
+
+impl<'de> Visitor<'de> for DateTimeVisitor {
+    type Value = DateTime;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(
+            "a number or numeric string of Unix epoch milliseconds, or a DateTime struct",
+        )
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<DateTime, E> {
+        DateTime::try_from_unix_epoch_milliseconds(value).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<DateTime, E> {
+        self.visit_i64(u64_to_milliseconds(value)?)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<DateTime, E> {
+        self.visit_i64(parse_milliseconds_str(value)?)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<DateTime, A::Error> {
+        let mut milliseconds = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "milliseconds" {
+                if milliseconds.is_some() {
+                    return Err(de::Error::duplicate_field("milliseconds"));
+                }
+                milliseconds = Some(map.next_value::<MillisecondsValue>()?.0);
+            } else {
+                map.next_value::<de::IgnoredAny>()?;
+            }
+        }
+        let milliseconds = milliseconds.ok_or_else(|| de::Error::missing_field("milliseconds"))?;
+        DateTime::try_from(milliseconds).map_err(de::Error::custom)
+    }
+}
+
+/// A milliseconds value that may be sent as either a number or a numeric string.
+struct MillisecondsValue(DateTimeUnit);
+
+impl<'de> Deserialize<'de> for MillisecondsValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MillisecondsVisitor;
+
+        impl<'de> Visitor<'de> for MillisecondsVisitor {
+            type Value = MillisecondsValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number or numeric string of milliseconds")
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<MillisecondsValue, E> {
+                Ok(MillisecondsValue(value))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<MillisecondsValue, E> {
+                Ok(MillisecondsValue(u64_to_milliseconds(value)?))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<MillisecondsValue, E> {
+                Ok(MillisecondsValue(parse_milliseconds_str(value)?))
+            }
+        }
+
+        deserializer.deserialize_any(MillisecondsVisitor)
     }
 }
 
 impl DateTime {
+    /// The first millisecond of AD 0, the earliest `DateTime`.
+    pub const MIN: DateTime = DateTime { milliseconds: 0 };
+
+    /// The last millisecond of 9999-12-31, the latest `DateTime` that can be formatted with a 4 digit year and parsed back.
+    /// Use `DateTime::MAX.fields()` to get its calendar date.
+    pub const MAX: DateTime = DateTime {
+        milliseconds: 315_569_433_599_999,
+    };
+
     /// Create a new `DateTime` from the current system time.
     /// Time is UTC.
     pub fn now() -> Self {
-        Self::from_milliseconds(now_milliseconds())
+        Self::from_unix_epoch_milliseconds(Self::now_unix_millis())
+    }
+
+    /// Returns the current system time in milliseconds since the Unix epoch (Midnight of Jan 1st, 1970).
+    pub fn now_unix_millis() -> DateTimeUnit {
+        Self::now_unix_millis_at(SystemTime::now())
+    }
+
+    /// Returns the clock reading `now` in milliseconds since the Unix epoch (Midnight of Jan 1st, 1970).
+    pub fn now_unix_millis_at(now: SystemTime) -> DateTimeUnit {
+        let now = now
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time before Unix epoch");
+        now.as_millis() as DateTimeUnit
+    }
+
+    /// Returns the current system time in whole seconds since the Unix epoch (Midnight of Jan 1st, 1970).
+    pub fn now_unix_seconds() -> DateTimeUnit {
+        Self::now_unix_seconds_at(SystemTime::now())
+    }
+
+    /// Returns the clock reading `now` in whole seconds since the Unix epoch (Midnight of Jan 1st, 1970).
+    pub fn now_unix_seconds_at(now: SystemTime) -> DateTimeUnit {
+        Self::now_unix_millis_at(now).div_euclid(1000)
+    }
+
+    /// Create a new `DateTime` from the current system time, truncated down to a multiple of `unit` (see `truncate_to()`).
+    /// e.g. `DateTime::now_truncated_to(Time::from_minutes(1))` is the start of the current minute.
+    pub fn now_truncated_to(unit: Time) -> Self {
+        Self::now_truncated_to_at(unit, SystemTime::now())
+    }
+
+    /// Create a new `DateTime` from the clock reading `now`, truncated down to a multiple of `unit` (see `truncate_to()`).
+    pub fn now_truncated_to_at(unit: Time, now: SystemTime) -> Self {
+        Self::from_unix_epoch_milliseconds(Self::now_unix_millis_at(now)).truncate_to(unit)
+    }
+
+    /// Create a new `DateTime` from the current system time, truncated to the start of the current second.
+    pub fn now_seconds() -> Self {
+        Self::now_seconds_at(SystemTime::now())
+    }
+
+    /// Create a new `DateTime` from the clock reading `now`, truncated to the start of its second.
+    pub fn now_seconds_at(now: SystemTime) -> Self {
+        Self::now_truncated_to_at(Time::from_seconds(1), now)
+    }
+
+    /// Create a new `DateTime` at the Unix epoch (Midnight of Jan 1st, 1970).
+    pub fn epoch() -> Self {
+        Self::from_milliseconds(EPOCH_START)
     }
 
     /// Create a new `DateTime` from the provided values.
+    ///
+    /// Debug builds panic with a descriptive message when a value is outside of its valid range,
+    /// while release builds skip the checks. Use `DateTime::try_new()` to get an error in all builds.
     pub fn new(year: u16, month: u8, day: u8, hour: u8, minutes: u8, seconds: u8) -> Self {
-        let is_leap_year = is_leap_year(year);
+        #[cfg(debug_assertions)]
+        if let Err(error) = check_ranges(year, month, day, hour, minutes, seconds) {
+            panic!("DateTime::new: {}", error);
+        }
+        let is_leap_year = has_leap_day(year);
         let year = year as DateTimeUnit;
         let year_offset = year;
         let mut days = year_offset * 365;
@@ -77,15 +358,497 @@ impl DateTime {
         Self { milliseconds }
     }
 
+    /// Create a new `DateTime` at midnight of the provided date.
+    /// Taking the month as a `Month` rules out invalid month numbers at compile time.
+    pub fn from_ym(year: u16, month: Month, day: u8) -> Self {
+        Self::new(year, month.number(), day, 0, 0, 0)
+    }
+
+    /// Create a new `DateTime` from the provided values, confirming the date falls on the `expected` weekday.
+    /// Useful when imported data includes both a date and its claimed weekday.
+    ///
+    /// Returns `TimeError::OutOfRange` for an invalid value and `TimeError::WeekdayMismatch` when the weekdays disagree.
+    pub fn try_new_checked_weekday(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minutes: u8,
+        seconds: u8,
+        expected: Weekday,
+    ) -> Result<DateTime, TimeError> {
+        let datetime = Self::try_new(year, month, day, hour, minutes, seconds)?;
+        let actual = datetime.weekday();
+        if actual != expected {
+            return Err(TimeError::WeekdayMismatch { expected, actual });
+        }
+        Ok(datetime)
+    }
+
+    /// Create a new `DateTime` from the provided values, returning an error when any value is outside of its valid range
+    /// (including a day past the end of its month, or a year after 9999).
+    pub fn try_new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minutes: u8,
+        seconds: u8,
+    ) -> Result<DateTime, TimeError> {
+        check_ranges(year, month, day, hour, minutes, seconds)?;
+        Ok(Self::new(year, month, day, hour, minutes, seconds))
+    }
+
+    /// Create a new `DateTime` at midnight of an ordinal date, where `day_of_year` 1 is January 1st.
+    /// Returns an error when `day_of_year` is outside of the year's 365 (or 366 in leap years) days.
+    pub fn from_ordinal(year: u16, day_of_year: u16) -> Result<DateTime, TimeError> {
+        let days_in_year = if has_leap_day(year) { 366 } else { 365 };
+        if day_of_year < 1 || day_of_year > days_in_year {
+            return Err(TimeError::OutOfRange {
+                field: "day_of_year",
+                value: day_of_year as i128,
+            });
+        }
+        Ok(Self::new(year, 1, 1, 0, 0, 0).add_days(day_of_year as i64 - 1))
+    }
+
+    /// Create a new `DateTime` from a local wall-clock time in the provided `offset` from UTC.
+    /// The offset is subtracted to get the UTC instant, so 14:00 at `+02:00` is stored as 12:00 UTC.
+    ///
+    /// `DateTime::new()` always treats its values as UTC, so use this method whenever the values are a local time.
+    /// A local time less than the offset after the start of AD 0 saturates to `DateTime::MIN`.
+    pub fn from_local(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minutes: u8,
+        seconds: u8,
+        offset: TimeZoneOffset,
+    ) -> Self {
+        let local = Self::new(year, month, day, hour, minutes, seconds);
+        Self::from_milliseconds(
+            local
+                .milliseconds
+                .saturating_sub(offset.to_milliseconds())
+                .max(0),
+        )
+    }
+
+    /// Returns a `DateTime` whose values show the local wall-clock time at the provided `offset` from UTC.
+    /// This is the reverse of `DateTime::from_local()`, and the returned value no longer represents the original UTC instant.
+    /// Wall-clock times before AD 0 or after `DateTime::MAX` saturate to `DateTime::MIN` or `DateTime::MAX`.
+    pub fn to_local(&self, offset: TimeZoneOffset) -> DateTime {
+        Self::from_milliseconds_saturating(
+            self.milliseconds.saturating_add(offset.to_milliseconds()),
+        )
+    }
+
+    /// Returns a `ZonedDateTime` that displays this instant as the wall-clock time at the provided `offset` from UTC.
+    pub fn in_timezone(&self, offset: TimeZoneOffset) -> ZonedDateTime {
+        ZonedDateTime::new(self.clone(), offset)
+    }
+
+    /// Create a new `DateTime` from parsed values, confirming each value is within its valid range.
+    pub(crate) fn from_parsed_parts(
+        year: u32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minutes: u32,
+        seconds: u32,
+        milliseconds: u32,
+    ) -> Result<Self, ParseError> {
+        fn check(field: &'static str, value: u32, max: u32) -> Result<(), ParseError> {
+            if value > max {
+                return Err(ParseError::OutOfRange {
+                    field,
+                    value: value as i64,
+                });
+            }
+            Ok(())
+        }
+        check("year", year, 9999)?;
+        if !(1..=12).contains(&month) {
+            return Err(ParseError::OutOfRange {
+                field: "month",
+                value: month as i64,
+            });
+        }
+        if day < 1 || day > days_in_month(year as u16, month as u8) as u32 {
+            return Err(ParseError::OutOfRange {
+                field: "day",
+                value: day as i64,
+            });
+        }
+        check("hour", hour, 23)?;
+        check("minute", minutes, 59)?;
+        check("second", seconds, 59)?;
+        check("millisecond", milliseconds, 999)?;
+        let datetime = Self::new(
+            year as u16,
+            month as u8,
+            day as u8,
+            hour as u8,
+            minutes as u8,
+            seconds as u8,
+        );
+        Ok(Self::from_milliseconds(
+            datetime.milliseconds + milliseconds as DateTimeUnit,
+        ))
+    }
+
+    /// Create a new `DateTime` by parsing the `input` using the same format options as `time_format()`:
+    /// - %Y = 1 to 4 digit year
+    /// - %m = 2 digit month
+    /// - %d = 2 digit day of month
+    /// - %H = 2 digit hour
+    /// - %M = 2 digit minute
+    /// - %S = 2 digit second
+    /// - %f = 1 to 9 digit fraction of a second, truncated to milliseconds
+    /// - %.f = optional `.` followed by a 1 to 9 digit fraction of a second, truncated to milliseconds
+    /// - %,f = `,` followed by a 1 to 9 digit fraction of a second, truncated to milliseconds
+    /// - %% = a literal `%`
+    ///
+    /// Text in single quotes (`'at'`) is literal, and `''` is a literal `'`, the same as when formatting.
+    /// Other `time_format()` fields, such as names and weekdays, return a `ParseError`.
+    /// All other text in the format must match the input exactly, except that a single trailing `Z` (UTC) is accepted
+    /// and ignored, as `DateTime` values are always UTC.
+    /// Values missing from the format default to the start of their range (year 0, January, the 1st, midnight, zero milliseconds).
+    ///
+    /// Values outside of their valid range are rejected, including a leap second (`:60`).
+    /// Use `DateTime::parse_with_mode()` with `ParseMode::Lenient` to clamp them instead.
+    ///
+    /// Example:
+    /// ```
+    /// use stoicdreams_timestamp::prelude::*;
+    ///
+    /// let datetime = DateTime::parse("2023-05-28 14:36:46.076", "%Y-%m-%d %H:%M:%S.%f").unwrap();
+    /// assert_eq!(datetime.to_unix_epoch_milliseconds(), 1_685_284_606_076);
+    /// ```
+    pub fn parse(input: &str, format: &str) -> Result<DateTime, ParseError> {
+        Self::parse_with_mode(input, format, ParseMode::Strict)
+    }
+
+    /// Create a new `DateTime` by parsing the `input` the same as `DateTime::parse()`, with out-of-range values handled by `mode`.
+    ///
+    /// `DateTime` cannot represent leap seconds, so `ParseMode::Lenient` clamps a seconds value of `60`
+    /// (e.g. `"2016-12-31 23:59:60"`) to the last millisecond before the next minute (`23:59:59.999`),
+    /// keeping it ordered before the following midnight. This is lossy, as the result no longer shows the leap second.
+    pub fn parse_with_mode(
+        input: &str,
+        format: &str,
+        mode: ParseMode,
+    ) -> Result<DateTime, ParseError> {
+        let mut parser = Parser::new(input);
+        let (mut year, mut month, mut day) = (0, 1, 1);
+        let (mut hour, mut minutes, mut seconds, mut milliseconds) = (0, 0, 0, 0);
+        // Scanned the same way as when formatting, so quoted text and `%%` are read back as literal text.
+        let format = CompiledFormat::compile(format);
+        for segment in format.segments() {
+            match segment {
+                Segment::Literal(text) => parser.expect_literal(text, "literal text")?,
+                Segment::Field(Specifier::Year | Specifier::UnpaddedYear) => {
+                    year = parser.digits_between(1, 4, "year")?.0
+                }
+                Segment::Field(Specifier::Month) => month = parser.digits(2, "2 digit month")?,
+                Segment::Field(Specifier::DayOfMonth) => day = parser.digits(2, "2 digit day")?,
+                Segment::Field(Specifier::Hour) => hour = parser.digits(2, "2 digit hour")?,
+                Segment::Field(Specifier::Minute) => {
+                    minutes = parser.digits(2, "2 digit minute")?
+                }
+                Segment::Field(Specifier::Second) => {
+                    seconds = parser.digits(2, "2 digit second")?
+                }
+                Segment::Field(Specifier::Fraction) => {
+                    milliseconds = parser.fraction_nanoseconds("fraction of a second")? / 1_000_000
+                }
+                Segment::Field(Specifier::TrimmedFraction) => {
+                    if parser.optional_byte(b'.') {
+                        milliseconds =
+                            parser.fraction_nanoseconds("fraction of a second")? / 1_000_000;
+                    }
+                }
+                Segment::Field(Specifier::CommaFraction) => {
+                    parser.expect_byte(b',', "','")?;
+                    milliseconds = parser.fraction_nanoseconds("fraction of a second")? / 1_000_000
+                }
+                Segment::Field(_) => {
+                    return Err(parser.unexpected("a field supported by DateTime::parse"))
+                }
+            }
+        }
+        if !parser.optional_byte(b'Z') {
+            parser.optional_byte(b'z');
+        }
+        parser.finish()?;
+        if mode == ParseMode::Lenient {
+            (seconds, milliseconds) = clamp_leap_second(seconds, milliseconds);
+            day = clamp_day_of_month(year, month, day);
+        }
+        Self::from_parsed_parts(year, month, day, hour, minutes, seconds, milliseconds)
+    }
+
+    /// Create a new `DateTime` from an RFC 3339 date and time, e.g. `"2023-05-28T14:36:46.076Z"`.
+    ///
+    /// The fraction of a second is optional and truncated (never rounded) to milliseconds, so `.123999999` is read as `.123`.
+    /// Truncating keeps the result at or before the instant written, and matches `DateTime::parse()`.
+    /// A `Z` suffix means UTC, while an offset such as `+02:00` is subtracted to get the UTC instant.
+    /// RFC 3339 allows leap seconds, so `:60` is clamped to `:59.999`, the same as `ParseMode::Lenient`.
+    ///
+    /// Parsing reads the input bytes in place and validates digits directly, so it never allocates.
+    pub fn from_rfc3339(value: &str) -> Result<DateTime, ParseError> {
+        let mut parser = Parser::new(value);
+        let year = parser.digits(4, "4 digit year")?;
+        parser.expect_byte(b'-', "'-'")?;
+        let month = parser.digits(2, "2 digit month")?;
+        parser.expect_byte(b'-', "'-'")?;
+        let day = parser.digits(2, "2 digit day")?;
+        if !(parser.optional_byte(b'T') || parser.optional_byte(b't')) {
+            parser.expect_byte(b' ', "'T'")?;
+        }
+        let hour = parser.digits(2, "2 digit hour")?;
+        parser.expect_byte(b':', "':'")?;
+        let minutes = parser.digits(2, "2 digit minute")?;
+        parser.expect_byte(b':', "':'")?;
+        let seconds = parser.digits(2, "2 digit second")?;
+        let mut milliseconds = 0;
+        if parser.optional_byte(b'.') {
+            milliseconds = parser.fraction_nanoseconds("fraction of a second")? / 1_000_000;
+        }
+        let offset = parse_utc_offset(&mut parser)?;
+        parser.finish()?;
+        let (seconds, milliseconds) = clamp_leap_second(seconds, milliseconds);
+        let local =
+            Self::from_parsed_parts(year, month, day, hour, minutes, seconds, milliseconds)?;
+        let milliseconds = local.milliseconds - offset.to_milliseconds();
+        if milliseconds < 0 {
+            return Err(ParseError::OutOfRange {
+                field: "datetime",
+                value: milliseconds,
+            });
+        }
+        Ok(Self::from_milliseconds(milliseconds))
+    }
+
+    /// Create a new `DateTime` from an iCalendar (RFC 5545) UTC date-time, as used by `DTSTAMP`, e.g. `"20230528T143646Z"`.
+    ///
+    /// Only the UTC form with a trailing `Z` is accepted, as floating and `TZID` times need a timezone database to resolve.
+    pub fn from_ics(value: &str) -> Result<DateTime, ParseError> {
+        let mut parser = Parser::new(value);
+        let year = parser.digits(4, "4 digit year")?;
+        let month = parser.digits(2, "2 digit month")?;
+        let day = parser.digits(2, "2 digit day")?;
+        parser.expect_byte(b'T', "'T'")?;
+        let hour = parser.digits(2, "2 digit hour")?;
+        let minutes = parser.digits(2, "2 digit minute")?;
+        let seconds = parser.digits(2, "2 digit second")?;
+        parser.expect_byte(b'Z', "'Z'")?;
+        parser.finish()?;
+        Self::from_parsed_parts(year, month, day, hour, minutes, seconds, 0)
+    }
+
+    /// Create a new `DateTime` by trying several common formats, returning the first successful parse.
+    ///
+    /// Formats are tried in this order:
+    /// 1. RFC 3339 (`DateTime::from_rfc3339()`), e.g. `"2023-05-28T14:36:46.076Z"` or `"2023-05-28T16:36:46+02:00"`
+    /// 2. The `DateTime::format()` layout `"%Y-%m-%d %H:%M:%S.%f"`
+    /// 3. `"%Y-%m-%d %H:%M:%S"`
+    /// 4. `"%Y-%m-%dT%H:%M:%S.%f"`, without a UTC offset
+    /// 5. `"%Y-%m-%dT%H:%M:%S"`, without a UTC offset
+    /// 6. `"%Y-%m-%d %H:%M"`
+    /// 7. `"%Y-%m-%d"`
+    /// 8. `"%Y/%m/%d"`
+    ///
+    /// Values without a UTC offset are treated as UTC.
+    /// When no format matches, the error from the RFC 3339 attempt is returned.
+    pub fn parse_flexible(value: &str) -> Result<DateTime, ParseError> {
+        const FORMATS: [&str; 7] = [
+            "%Y-%m-%d %H:%M:%S.%f",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S.%f",
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%d",
+            "%Y/%m/%d",
+        ];
+        let error = match Self::from_rfc3339(value) {
+            Ok(datetime) => return Ok(datetime),
+            Err(error) => error,
+        };
+        FORMATS
+            .iter()
+            .find_map(|format| Self::parse(value, format).ok())
+            .ok_or(error)
+    }
+
+    /// Create a new `DateTime` from an HTTP date, as used in `Date` and `Last-Modified` headers.
+    ///
+    /// Supports the preferred IMF-fixdate format (`"Sun, 28 May 2023 14:36:46 GMT"`), as well as the obsolete
+    /// RFC 850 (`"Sunday, 28-May-23 14:36:46 GMT"`) and asctime (`"Sun May 28 14:36:46 2023"`) formats.
+    /// Two digit RFC 850 years from 70 to 99 are read as 19xx, and from 00 to 69 as 20xx.
+    /// The weekday name must be valid but is not checked against the date.
+    pub fn from_http_date(value: &str) -> Result<DateTime, ParseError> {
+        let mut parser = Parser::new(value);
+        let weekday_position = parser.position();
+        let weekday = parser.alphabetic("weekday name")?;
+        if Weekday::from_name(weekday).is_none() {
+            return Err(ParseError::Unexpected {
+                position: weekday_position,
+                expected: "weekday name",
+            });
+        }
+        // The asctime form puts its year last, so it stays `None` until after the time.
+        let (year, month, day): (Option<u32>, u32, u32);
+        if parser.optional_byte(b',') {
+            parser.expect_byte(b' ', "space")?;
+            day = parser.digits(2, "2 digit day")?;
+            if parser.optional_byte(b'-') {
+                month = parse_month_name(&mut parser)?;
+                parser.expect_byte(b'-', "'-'")?;
+                let short_year = parser.digits(2, "2 digit year")?;
+                year = Some(if short_year < 70 {
+                    2000 + short_year
+                } else {
+                    1900 + short_year
+                });
+            } else {
+                parser.expect_byte(b' ', "space")?;
+                month = parse_month_name(&mut parser)?;
+                parser.expect_byte(b' ', "space")?;
+                year = Some(parser.digits(4, "4 digit year")?);
+            }
+            parser.expect_byte(b' ', "space")?;
+        } else {
+            parser.expect_byte(b' ', "',' or space")?;
+            month = parse_month_name(&mut parser)?;
+            parser.expect_byte(b' ', "space")?;
+            parser.optional_byte(b' ');
+            day = parser.digits_between(1, 2, "day")?.0;
+            parser.expect_byte(b' ', "space")?;
+            year = None;
+        }
+        let hour = parser.digits(2, "2 digit hour")?;
+        parser.expect_byte(b':', "':'")?;
+        let minutes = parser.digits(2, "2 digit minute")?;
+        parser.expect_byte(b':', "':'")?;
+        let seconds = parser.digits(2, "2 digit second")?;
+        parser.expect_byte(b' ', "space")?;
+        let year = match year {
+            Some(year) => {
+                parser.expect_literal("GMT", "GMT")?;
+                year
+            }
+            None => parser.digits(4, "4 digit year")?,
+        };
+        parser.finish()?;
+        Self::from_parsed_parts(year, month, day, hour, minutes, seconds, 0)
+    }
+
     /// Create a new `DateTime` from a Unix Epoch timestamp (milliseconds).
     /// This is the same as `SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis()`.
     /// This is the same as Javascript's `Date.now()`.
+    /// Every value from `-EPOCH_START` (the start of AD 0) through `DateTime::MAX` round-trips exactly through `to_unix_epoch_milliseconds()`.
+    /// Panics for values after `DateTime::MAX`, see `try_from_unix_epoch_milliseconds()` to get an error instead.
     pub fn from_unix_epoch_milliseconds(milliseconds: DateTimeUnit) -> Self {
-        let milliseconds = milliseconds + EPOCH_START;
-        if milliseconds < 0 {
-            panic!("DateTime does not yet support dates before AD 0");
+        match milliseconds.checked_add(EPOCH_START) {
+            Some(milliseconds) if milliseconds <= Self::MAX.milliseconds => {
+                Self::from_milliseconds(milliseconds)
+            }
+            _ => panic!("DateTime does not support dates after DateTime::MAX"),
         }
-        Self { milliseconds }
+    }
+
+    /// Create a new `DateTime` from a Unix Epoch timestamp in nanoseconds, as emitted by kernel and tracing sources.
+    /// Nanoseconds are truncated down to the start of their millisecond, so use `PreciseDateTime` when full precision is needed.
+    pub fn from_unix_epoch_nanoseconds(nanoseconds: i128) -> Self {
+        let milliseconds = nanoseconds.div_euclid(1_000_000);
+        match DateTimeUnit::try_from(milliseconds) {
+            Ok(milliseconds) => Self::from_unix_epoch_milliseconds(milliseconds),
+            Err(_) => panic!("DateTime does not support Unix nanoseconds {}", nanoseconds),
+        }
+    }
+
+    /// Create a new `DateTime` from a Unix Epoch timestamp (milliseconds), returning an error instead of panicking
+    /// when the value is before AD 0 or after `DateTime::MAX`.
+    pub fn try_from_unix_epoch_milliseconds(milliseconds: DateTimeUnit) -> Result<Self, TimeError> {
+        let out_of_range = TimeError::OutOfRange {
+            field: "milliseconds",
+            value: milliseconds as i128,
+        };
+        match milliseconds.checked_add(EPOCH_START) {
+            Some(milliseconds) if (0..=Self::MAX.milliseconds).contains(&milliseconds) => {
+                Ok(Self { milliseconds })
+            }
+            _ => Err(out_of_range),
+        }
+    }
+
+    /// Create a new `DateTime` from typed milliseconds since the Unix epoch, returning an error when the value is
+    /// before AD 0 or after `DateTime::MAX`. The same as `try_from_unix_epoch_milliseconds()`.
+    pub fn from_unix_millis(milliseconds: UnixMillis) -> Result<Self, TimeError> {
+        Self::try_from_unix_epoch_milliseconds(milliseconds.0)
+    }
+
+    /// Create a new `DateTime` from typed milliseconds since the start of AD 0, returning an error when the value is
+    /// before AD 0 or after `DateTime::MAX`.
+    pub fn from_ad_millis(milliseconds: AdMillis) -> Result<Self, TimeError> {
+        Self::try_from(milliseconds.0)
+    }
+
+    /// Create a new `DateTime` from fractional seconds since the Unix epoch (e.g. `1685284606.076`), rounded to the nearest millisecond.
+    /// An `f64` holds about 15 significant digits, so values are exact to the millisecond only until roughly the year 287,000;
+    /// all dates up to `DateTime::MAX` are well within that.
+    ///
+    /// Values outside of `DateTime::MIN` through `DateTime::MAX` are clamped to that range, and NaN or infinite values return a `TimeError`.
+    pub fn from_unix_epoch_seconds_f64(seconds: f64) -> Result<Self, TimeError> {
+        if !seconds.is_finite() {
+            return Err(TimeError::NotFinite { field: "seconds" });
+        }
+        let milliseconds = (seconds * 1000.0).round() + EPOCH_START as f64;
+        let milliseconds = milliseconds.clamp(0.0, Self::MAX.milliseconds as f64);
+        Ok(Self::from_milliseconds(milliseconds as DateTimeUnit))
+    }
+
+    /// Create a new `DateTime` from a Windows `FILETIME` value, counting 100 nanosecond intervals since 1601-01-01 UTC.
+    /// Precision finer than a millisecond is truncated, and values after `DateTime::MAX` are saturated to `DateTime::MAX`.
+    pub fn from_filetime(ticks: u64) -> Self {
+        let milliseconds = (ticks / 10_000) as DateTimeUnit;
+        Self::from_milliseconds_saturating(milliseconds + FILETIME_EPOCH_START)
+    }
+
+    /// Create a new `DateTime` from .NET `DateTime.Ticks`, counting 100 nanosecond intervals since 0001-01-01 UTC.
+    /// Precision finer than a millisecond is truncated, and values are saturated to `DateTime::MIN` through `DateTime::MAX`.
+    pub fn from_dotnet_ticks(ticks: i64) -> Self {
+        Self::from_milliseconds_saturating(ticks / 10_000 + DOTNET_EPOCH_START)
+    }
+
+    /// Create a new `DateTime` from an Excel serial date, where the whole part counts days and the fraction is the time of day.
+    ///
+    /// Excel treats 1900 as a leap year, so serials count from 1899-12-30 from serial 61 (1900-03-01) onwards, while serials 1
+    /// (1900-01-01) through 59 (1900-02-28) count from 1899-12-31. The phantom serial 60 (1900-02-29) maps to 1900-02-28.
+    /// The time is rounded to the nearest millisecond.
+    ///
+    /// Values outside of `DateTime::MIN` through `DateTime::MAX` are clamped to that range, and NaN or infinite values return a `TimeError`.
+    pub fn from_excel_serial(serial: f64) -> Result<Self, TimeError> {
+        const DAY: f64 = (24 * 60 * 60 * 1000) as f64;
+        if !serial.is_finite() {
+            return Err(TimeError::NotFinite { field: "serial" });
+        }
+        let serial = if serial < 60.0 { serial + 1.0 } else { serial };
+        let milliseconds = (serial * DAY).round() as DateTimeUnit;
+        Ok(Self::from_milliseconds_saturating(
+            milliseconds.saturating_add(EXCEL_EPOCH_START),
+        ))
+    }
+
+    /// Create a new `DateTime` at midnight (UTC) of an epoch day, the number of days since the Unix epoch (see `epoch_day()`).
+    /// Days are saturated to `DateTime::MIN` through `DateTime::MAX`.
+    pub fn from_epoch_day(day: i64) -> Self {
+        let milliseconds = day
+            .saturating_mul(24 * 60 * 60 * 1000)
+            .saturating_add(EPOCH_START);
+        Self::from_milliseconds_saturating(milliseconds)
     }
 
     /// Create a new `DateTime` from milliseconds since the start of AD 0.
@@ -96,6 +859,13 @@ impl DateTime {
         Self { milliseconds }
     }
 
+    /// Create a new `DateTime` from milliseconds since the start of AD 0, saturated to `DateTime::MIN` through `DateTime::MAX`.
+    fn from_milliseconds_saturating(milliseconds: DateTimeUnit) -> Self {
+        Self {
+            milliseconds: milliseconds.clamp(0, Self::MAX.milliseconds),
+        }
+    }
+
     pub fn get_milliseconds_of_second(&self) -> u16 {
         (self.milliseconds % 1000) as u16
     }
@@ -113,179 +883,2744 @@ impl DateTime {
     }
 
     pub fn get_day_of_month(&self) -> u16 {
-        let year = self.get_year();
-        let mut days = self.get_day_of_year() + 1;
-        let days_in_month = [
-            31u16,
-            if is_leap_year(year) { 29 } else { 28 },
-            31,
-            30,
-            31,
-            30,
-            31,
-            31,
-            30,
-            31,
-            30,
-            31,
-        ];
-        let mut month = 0;
-        while days > days_in_month[month] {
-            days -= days_in_month[month];
-            month += 1;
-        }
-        days
+        let (year, day_of_year) = self.year_and_day_of_year();
+        month_and_day(year, day_of_year).1
     }
 
     pub fn get_month(&self) -> u8 {
-        let year = self.get_year();
-        let day_of_year = self.get_day_of_year();
-        match is_leap_year(year) {
-            true => match day_of_year {
-                1..=31 => 1,
-                32..=60 => 2,
-                61..=91 => 3,
-                92..=121 => 4,
-                122..=152 => 5,
-                153..=182 => 6,
-                183..=213 => 7,
-                214..=244 => 8,
-                245..=274 => 9,
-                275..=305 => 10,
-                306..=335 => 11,
-                336..=366 => 12,
-                _ => 0,
-            },
-            false => match day_of_year {
-                1..=31 => 1,
-                32..=59 => 2,
-                60..=90 => 3,
-                91..=120 => 4,
-                121..=151 => 5,
-                152..=181 => 6,
-                182..=212 => 7,
-                213..=243 => 8,
-                244..=273 => 9,
-                274..=304 => 10,
-                305..=334 => 11,
-                335..=365 => 12,
-                _ => 0,
-            },
-        }
+        let (year, day_of_year) = self.year_and_day_of_year();
+        month_and_day(year, day_of_year).0
     }
 
+    /// Returns the ordinal day of the year, where 1 is January 1st and 365 (or 366 in leap years) is December 31st.
     pub fn get_day_of_year(&self) -> u16 {
-        let mut days = self.to_days();
-        let mut year = 0;
-        while days > 365 {
-            days -= if year > 0 && is_leap_year(year) {
-                366
-            } else {
-                365
-            };
-            year += 1;
+        self.year_and_day_of_year().1
+    }
+
+    /// Returns the year and the ordinal day of the year (starting at 1) in a single pass.
+    ///
+    /// Year 0 is counted as a common year of 365 days (see `days_in_month()`), and every year after follows the Gregorian calendar.
+    fn year_and_day_of_year(&self) -> (u16, u16) {
+        year_and_day_of_year(self.to_days())
+    }
+
+    /// Returns true when this `DateTime` is exactly midnight, with every time of day component (down to milliseconds) zero.
+    pub fn is_midnight(&self) -> bool {
+        self.milliseconds % (24 * 60 * 60 * 1000) == 0
+    }
+
+    /// Returns true when this date is the 1st of its month.
+    pub fn is_first_day_of_month(&self) -> bool {
+        self.get_day_of_month() == 1
+    }
+
+    /// Returns true when this date is the last day of its month, accounting for leap years.
+    pub fn is_last_day_of_month(&self) -> bool {
+        let (year, day_of_year) = self.year_and_day_of_year();
+        let (month, day) = month_and_day(year, day_of_year);
+        day == days_in_month(year, month) as u16
+    }
+
+    /// Returns the `(year, month, day)` of this date in the provided calendar system.
+    /// `CalendarSystem::Gregorian` matches the `get_*` methods, which always use the proleptic Gregorian calendar.
+    pub fn calendar_date(&self, system: CalendarSystem) -> (u16, u8, u16) {
+        match system {
+            CalendarSystem::Gregorian => {
+                let (year, day_of_year) = self.year_and_day_of_year();
+                let (month, day) = month_and_day(year, day_of_year);
+                (year, month, day)
+            }
+            CalendarSystem::Julian => self.to_julian_calendar_date(),
+        }
+    }
+
+    /// Returns the `(year, month, day)` of this date in the Julian calendar.
+    pub fn to_julian_calendar_date(&self) -> (u16, u8, u16) {
+        const UNIX_EPOCH_JULIAN_DAY_NUMBER: DateTimeUnit = 2_440_588;
+        let unix_days = self
+            .to_unix_epoch_milliseconds()
+            .div_euclid(24 * 60 * 60 * 1000);
+        // Converts the Julian day number to a Julian calendar date, counting years from March so leap days fall last.
+        let c = unix_days + UNIX_EPOCH_JULIAN_DAY_NUMBER + 32_082;
+        let d = (4 * c + 3) / 1461;
+        let e = c - 1461 * d / 4;
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = d - 4800 + m / 10;
+        (year as u16, month as u8, day as u16)
+    }
+
+    /// Formats this `DateTime` like `format()`, but with the date shown in the provided calendar system.
+    pub fn format_in_calendar(&self, system: CalendarSystem) -> String {
+        let (year, month, day) = self.calendar_date(system);
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+            year,
+            month,
+            day,
+            self.get_hour_of_day(),
+            self.get_minutes_of_hour(),
+            self.get_seconds_of_minute(),
+            self.get_milliseconds_of_second()
+        )
+    }
+
+    /// Returns all calendar and clock fields, decomposing the stored milliseconds only once.
+    /// The day of the week and day of the year come from the same whole-day count as the date, rather than being recomputed.
+    pub fn fields(&self) -> DateFields {
+        const MILLISECONDS_PER_DAY: DateTimeUnit = 24 * 60 * 60 * 1000;
+        let days = self.milliseconds / MILLISECONDS_PER_DAY;
+        let time_of_day = self.milliseconds % MILLISECONDS_PER_DAY;
+        let (year, day_of_year) = year_and_day_of_year(days);
+        let (month, day) = month_and_day(year, day_of_year);
+        DateFields {
+            year,
+            month,
+            day,
+            hour: (time_of_day / (60 * 60 * 1000)) as u8,
+            minute: ((time_of_day / (60 * 1000)) % 60) as u8,
+            second: ((time_of_day / 1000) % 60) as u8,
+            millisecond: (time_of_day % 1000) as u16,
+            day_of_week: (days % 7) as u8,
+            day_of_year,
         }
-        days as u16
     }
 
     /// Returns the day of the week, where 0 is Sunday and 6 is Saturday.
     pub fn get_day_of_week(&self) -> u8 {
-        const AD_ZERO_OFFSET: i64 = 3;
-        let days = self.to_days() + AD_ZERO_OFFSET;
-        ((days + 4) % 7) as u8
+        // Jan 1st of AD 1 was a Monday, so Jan 1st of the 365 day year 0 (day 0) falls on a Sunday.
+        (self.to_days() % 7) as u8
     }
 
-    pub fn get_year(&self) -> u16 {
-        let mut days = self.to_days();
-        let mut year = 0;
-        while days > 365 {
-            days -= if is_leap_year(year) { 366 } else { 365 };
-            year += 1;
+    /// Returns the ISO 8601 day of the week, where 1 is Monday and 7 is Sunday.
+    /// This matches the output of the Unix command `date +%u`.
+    pub fn unix_weekday(&self) -> u8 {
+        match self.get_day_of_week() {
+            0 => 7,
+            day => day,
         }
-        year
     }
 
-    pub fn format(&self) -> String {
-        time_format(self.milliseconds, "%Y-%m-%d %H:%M:%S.%f")
+    /// Returns the day of the week as a `Weekday`.
+    pub fn weekday(&self) -> Weekday {
+        Weekday::from_day_of_week(self.get_day_of_week()).unwrap_or(Weekday::Sunday)
     }
 
-    /// Returns the total number of milliseconds since the Unix epoch.
-    pub fn to_unix_epoch_milliseconds(&self) -> DateTimeUnit {
-        self.milliseconds - EPOCH_START
+    /// Returns true when this date falls on a Saturday or Sunday.
+    pub fn is_weekend(&self) -> bool {
+        matches!(self.get_day_of_week(), 0 | 6)
     }
 
-    /// Returns the total number of milliseconds since 0 AD.
-    pub fn to_milliseconds(&self) -> DateTimeUnit {
-        self.milliseconds
+    /// Returns the number of business days (Monday through Friday) from the 1st of the month through this date.
+    /// A weekend date counts the business days before it, so it is 0 on a weekend that starts the month.
+    pub fn business_day_of_month(&self) -> u8 {
+        let day = self.get_day_of_month() as i64;
+        let first = self.add_days(1 - day);
+        (0..day)
+            .filter(|offset| !first.add_days(*offset).is_weekend())
+            .count() as u8
     }
 
-    /// Returns the total number of seconds since 0 AD.
-    pub fn to_seconds(&self) -> DateTimeUnit {
-        self.milliseconds / 1000
+    /// Create a new `DateTime` at midnight of the `n`th business day (Monday through Friday) of the month,
+    /// where `n` 1 is the first business day, as used for payroll dates.
+    /// Returns an error when the month is invalid or does not have `n` business days.
+    pub fn nth_business_day_of_month(year: u16, month: u8, n: u8) -> Result<DateTime, TimeError> {
+        let first = Self::try_new(year, month, 1, 0, 0, 0)?;
+        (0..days_in_month(year, month) as i64)
+            .map(|offset| first.add_days(offset))
+            .filter(|date| !date.is_weekend())
+            .nth((n as usize).wrapping_sub(1))
+            .ok_or(TimeError::OutOfRange {
+                field: "business_day",
+                value: n as i128,
+            })
     }
 
-    /// Returns the total number of minutes since 0 AD.
-    pub fn to_minutes(&self) -> DateTimeUnit {
-        self.milliseconds / (60 * 1000)
+    pub fn get_year(&self) -> u16 {
+        self.year_and_day_of_year().0
     }
 
-    /// Returns the total number of hours since 0 AD.
-    pub fn to_hours(&self) -> DateTimeUnit {
-        self.milliseconds / (60 * 60 * 1000)
+    pub fn format(&self) -> String {
+        static DEFAULT_FORMAT: OnceLock<CompiledFormat> = OnceLock::new();
+        let format = DEFAULT_FORMAT.get_or_init(|| CompiledFormat::compile(DEFAULT_LAYOUT));
+        format.format(self, self.get_milliseconds_of_second() as u32, 3)
     }
 
-    /// Returns the total number of days since 0 AD, starting at 1.
-    pub fn to_days(&self) -> DateTimeUnit {
-        self.milliseconds / (24 * 60 * 60 * 1000)
+    /// Formats this `DateTime` with a custom `format` (see `time_format()` for the supported tokens).
+    /// When `format` has no `%` tokens or `'` quotes it is returned borrowed, without allocating.
+    pub fn format_cow<'a>(&self, format: &'a str) -> Cow<'a, str> {
+        if !format.contains(['%', '\'']) {
+            return Cow::Borrowed(format);
+        }
+        Cow::Owned(time_format(self.milliseconds, format))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Formats this `DateTime` with a custom `format` (see `time_format()` for the supported tokens),
+    /// split into literal text and fields so renderers can style each field separately.
+    pub fn format_parts(&self, format: &str) -> Vec<FormatPart> {
+        format_parts(self, format)
+    }
 
-    #[test]
-    fn test_datetime_formatting_from_new() {
-        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
-        let utc_formatted = "2023-05-28 14:36:46.000";
-        assert_eq!(2023, datetime.get_year());
-        assert_eq!(5, datetime.get_month());
-        assert_eq!(28, datetime.get_day_of_month());
-        assert_eq!(14, datetime.get_hour_of_day());
-        assert_eq!(36, datetime.get_minutes_of_hour());
-        assert_eq!(46, datetime.get_seconds_of_minute());
-        assert_eq!(0, datetime.get_day_of_week());
-        assert_eq!(utc_formatted, datetime.format());
+    /// Returns a new `DateTime` moved by the provided number of days, which may be negative.
+    /// Results are saturated to `DateTime::MIN` through `DateTime::MAX`.
+    pub fn add_days(&self, days: i64) -> DateTime {
+        let offset = days.saturating_mul(24 * 60 * 60 * 1000);
+        Self::from_milliseconds_saturating(self.milliseconds.saturating_add(offset))
     }
 
-    #[test]
-    fn test_datetime_formatting_pre_1970() {
-        let datetime = DateTime::new(1903, 12, 25, 18, 36, 46);
-        let utc_formatted = "1903-12-25 18:36:46.000";
-        assert_eq!(utc_formatted, datetime.format());
-        assert_eq!(1903, datetime.get_year());
-        assert_eq!(12, datetime.get_month());
-        assert_eq!(25, datetime.get_day_of_month());
-        assert_eq!(18, datetime.get_hour_of_day());
-        assert_eq!(36, datetime.get_minutes_of_hour());
-        assert_eq!(5, datetime.get_day_of_week());
-        assert_eq!(46, datetime.get_seconds_of_minute());
+    /// Returns a new `DateTime` moved forward by `time`, e.g. "one week from now".
+    /// Results after `DateTime::MAX` are saturated to `DateTime::MAX`.
+    pub fn add(&self, time: &Time) -> DateTime {
+        let offset = DateTimeUnit::try_from(time.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+        Self::from_milliseconds_saturating(self.milliseconds.saturating_add(offset))
     }
 
-    #[test]
-    fn test_datetime_formatting_from_milliseconds() {
-        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
-        let utc_formatted = "2023-05-28 14:36:46.076";
-        assert_eq!(utc_formatted, datetime.format());
-        assert_eq!(2023, datetime.get_year());
-        assert_eq!(5, datetime.get_month());
-        assert_eq!(28, datetime.get_day_of_month());
+    /// Returns a new `DateTime` moved back by `time`, e.g. "three hours ago".
+    /// Results that would fall before AD 0 are saturated to AD 0, the same as `add_days()`.
+    pub fn subtract(&self, time: &Time) -> DateTime {
+        let offset = DateTimeUnit::try_from(time.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+        Self {
+            milliseconds: self.milliseconds.saturating_sub(offset).max(0),
+        }
+    }
+
+    /// Returns a new `DateTime` moved by the provided number of days, which may be negative.
+    /// Returns `None` when the result would fall before AD 0 or after `DateTime::MAX`, unlike `add_days()` which saturates.
+    pub fn checked_add_days(&self, days: i64) -> Option<DateTime> {
+        let milliseconds = days
+            .checked_mul(24 * 60 * 60 * 1000)
+            .and_then(|offset| self.milliseconds.checked_add(offset))?;
+        if !(0..=Self::MAX.milliseconds).contains(&milliseconds) {
+            return None;
+        }
+        Some(Self { milliseconds })
+    }
+
+    /// Returns a new `DateTime` moved forward by a `PreciseTime` offset.
+    /// `DateTime` only stores milliseconds, so any sub-millisecond part of the offset is truncated (e.g. 1.5 ms adds 1 ms).
+    /// Results after `DateTime::MAX` are saturated to `DateTime::MAX`.
+    pub fn add_precise_truncating(&self, offset: &PreciseTime) -> DateTime {
+        let milliseconds =
+            DateTimeUnit::try_from(offset.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+        Self::from_milliseconds_saturating(self.milliseconds.saturating_add(milliseconds))
+    }
+
+    /// Returns a new `DateTime` at the start of the provided hour on the same day.
+    fn at_hour_of_day(&self, hour: u8) -> DateTime {
+        let start_of_day = self.milliseconds - self.milliseconds_of_day();
+        Self {
+            milliseconds: start_of_day + hour as DateTimeUnit * 60 * 60 * 1000,
+        }
+    }
+
+    /// Returns this `DateTime` snapped into business hours, where business hours are weekdays from `start_hour` up to `end_hour`.
+    ///
+    /// - Times within business hours are returned unchanged.
+    /// - Weekday times before `start_hour` are moved to `start_hour` of the same day.
+    /// - Times after `end_hour` or on weekends are moved to `start_hour` of the next weekday.
+    pub fn clamp_to_business_hours(&self, start_hour: u8, end_hour: u8) -> DateTime {
+        let hour = self.get_hour_of_day();
+        if !self.is_weekend() && hour < start_hour {
+            return self.at_hour_of_day(start_hour);
+        }
+        if !self.is_weekend() && hour < end_hour {
+            return self.clone();
+        }
+        let mut next_day = self.add_days(1).at_hour_of_day(start_hour);
+        while next_day.is_weekend() {
+            next_day = next_day.add_days(1);
+        }
+        next_day
+    }
+
+    /// Formats this `DateTime` as an HTTP IMF-fixdate, as used in `Date` and `Last-Modified` headers.
+    /// e.g. `"Sun, 28 May 2023 14:36:46 GMT"`
+    ///
+    /// HTTP dates are always UTC, so the output always ends with `GMT`.
+    pub fn to_http_date(&self) -> String {
+        let month = Month::from_number(self.get_month()).unwrap_or(Month::January);
+        format!(
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            self.weekday().short_name(),
+            self.get_day_of_month(),
+            month.short_name(),
+            self.get_year(),
+            self.get_hour_of_day(),
+            self.get_minutes_of_hour(),
+            self.get_seconds_of_minute()
+        )
+    }
+
+    /// Returns the absolute time between `earlier` and this `DateTime`, so the argument order never matters.
+    /// Use `signed_duration_since()` when the direction matters.
+    pub fn duration_since(&self, earlier: &DateTime) -> Time {
+        Time::from_milliseconds(self.signed_duration_since(earlier).unsigned_abs())
+    }
+
+    /// Returns the milliseconds from `earlier` until this `DateTime`, which is negative when `earlier` is actually later.
+    pub fn signed_duration_since(&self, earlier: &DateTime) -> i64 {
+        self.milliseconds - earlier.milliseconds
+    }
+
+    /// Returns the time from `earlier` until this `DateTime`, or `None` when `earlier` is actually later.
+    /// This mirrors `std::time::Instant::checked_duration_since()`.
+    pub fn checked_duration_since(&self, earlier: &DateTime) -> Option<Time> {
+        let milliseconds = self.milliseconds.checked_sub(earlier.milliseconds)?;
+        TimeUnit::try_from(milliseconds)
+            .ok()
+            .map(Time::from_milliseconds)
+    }
+
+    /// Returns the time from `earlier` until this `DateTime`, or `Time::ZERO` when `earlier` is actually later.
+    /// This mirrors `std::time::Instant::saturating_duration_since()`.
+    pub fn saturating_duration_since(&self, earlier: &DateTime) -> Time {
+        self.checked_duration_since(earlier).unwrap_or(Time::ZERO)
+    }
+
+    /// Formats the time from `start` until this `DateTime` as a relative log prefix, e.g. `"+1.234s"`.
+    /// Times before `start` use a `-` sign instead, e.g. `"-0.500s"`.
+    pub fn format_elapsed_since(&self, start: &DateTime) -> String {
+        let elapsed = self.milliseconds - start.milliseconds;
+        let sign = if elapsed < 0 { '-' } else { '+' };
+        let elapsed = elapsed.unsigned_abs();
+        format!("{}{}.{:03}s", sign, elapsed / 1000, elapsed % 1000)
+    }
+
+    /// Compares this `DateTime` to `other`, treating them as `Equal` when they are no more than `tolerance` apart.
+    /// This is useful when comparing times recorded by machines whose clocks may be slightly skewed.
+    pub fn cmp_with_tolerance(&self, other: &DateTime, tolerance: Time) -> Ordering {
+        let difference = self.milliseconds.abs_diff(other.milliseconds);
+        if difference <= tolerance.to_milliseconds() {
+            return Ordering::Equal;
+        }
+        self.milliseconds.cmp(&other.milliseconds)
+    }
+
+    /// Formats this `DateTime` as an iCalendar (RFC 5545) UTC date-time, as used by `DTSTAMP`.
+    /// e.g. `"20230528T143646Z"`
+    ///
+    /// iCalendar date-times have no fraction of a second, so milliseconds are truncated.
+    pub fn to_ics(&self) -> String {
+        time_format(self.milliseconds, "%Y%m%dT%H%M%SZ")
+    }
+
+    /// Formats this `DateTime` as an RFC 3339 UTC date and time with milliseconds.
+    /// e.g. `"2023-05-28T14:36:46.076Z"`
+    ///
+    /// The output can be read back with `DateTime::from_rfc3339()` or `str::parse()`.
+    pub fn to_rfc3339(&self) -> String {
+        time_format(self.milliseconds, "%Y-%m-%dT%H:%M:%S.%fZ")
+    }
+
+    /// Formats this `DateTime` as an ISO 8601 UTC date and time with milliseconds, the same as `to_rfc3339()`.
+    /// e.g. `"2023-05-28T14:36:46.076Z"`
+    pub fn to_iso8601(&self) -> String {
+        self.to_rfc3339()
+    }
+
+    /// Returns true when this `DateTime` is within the half-open interval `[start, end)`,
+    /// meaning `start` is included and `end` is excluded.
+    pub fn in_half_open(&self, start: &DateTime, end: &DateTime) -> bool {
+        start.milliseconds <= self.milliseconds && self.milliseconds < end.milliseconds
+    }
+
+    /// Returns the next `DateTime` on or after this one with the provided wall-clock (UTC) `hour` and `minute`.
+    /// This is later today when that time has not yet passed, otherwise it is tomorrow.
+    pub fn next_daily(&self, hour: u8, minute: u8) -> DateTime {
+        let today = Self {
+            milliseconds: self.at_hour_of_day(hour).milliseconds
+                + minute as DateTimeUnit * 60 * 1000,
+        };
+        if today.milliseconds < self.milliseconds {
+            return today.add_days(1);
+        }
+        today
+    }
+
+    /// Returns the time remaining until the next midnight (UTC).
+    /// At exactly midnight the next midnight is a full day away.
+    pub fn until_next_day(&self) -> Time {
+        self.until_next_multiple_of(24 * 60 * 60 * 1000)
+    }
+
+    /// Returns the time remaining until the start of the next hour.
+    /// At exactly the start of an hour the next hour is a full hour away.
+    pub fn until_next_hour(&self) -> Time {
+        self.until_next_multiple_of(60 * 60 * 1000)
+    }
+
+    /// Returns the time remaining until the start of the next minute.
+    /// At exactly the start of a minute the next minute is a full minute away.
+    pub fn until_next_minute(&self) -> Time {
+        self.until_next_multiple_of(60 * 1000)
+    }
+
+    /// Returns the time remaining until the stored milliseconds next reach a multiple of `unit` milliseconds.
+    fn until_next_multiple_of(&self, unit: DateTimeUnit) -> Time {
+        Time::from_milliseconds((unit - self.milliseconds % unit) as TimeUnit)
+    }
+
+    /// Returns the number of whole `unit`s from this `DateTime` until `other`.
+    /// The result is negative when `other` is before this `DateTime`.
+    ///
+    /// `CalendarUnit::Years` and `CalendarUnit::Months` count whole calendar months, so Jan 15th until Feb 15th is one month.
+    /// All other units divide the difference in milliseconds by the fixed length of the unit.
+    pub fn count_between(&self, other: &DateTime, unit: CalendarUnit) -> i64 {
+        if other.milliseconds < self.milliseconds {
+            return -other.count_between(self, unit);
+        }
+        let difference = other.milliseconds - self.milliseconds;
+        match unit {
+            CalendarUnit::Years => self.months_until(other) / 12,
+            CalendarUnit::Months => self.months_until(other),
+            CalendarUnit::Weeks => difference / (7 * 24 * 60 * 60 * 1000),
+            CalendarUnit::Days => difference / (24 * 60 * 60 * 1000),
+            CalendarUnit::Hours => difference / (60 * 60 * 1000),
+            CalendarUnit::Minutes => difference / (60 * 1000),
+            CalendarUnit::Seconds => difference / 1000,
+        }
+    }
+
+    /// Returns the time between this `DateTime` and `now` as a single whole unit for compact displays,
+    /// e.g. `"2y"`, `"3mo"`, `"5d"`, `"4h"`, `"10m"`, or `"30s"`.
+    ///
+    /// The largest unit with at least one whole count is used, with years and months counted as calendar months.
+    /// Dates after `now` are measured the same way, so the result never has a sign.
+    pub fn coarse_age(&self, now: &DateTime) -> String {
+        const UNITS: [(CalendarUnit, &str); 6] = [
+            (CalendarUnit::Years, "y"),
+            (CalendarUnit::Months, "mo"),
+            (CalendarUnit::Days, "d"),
+            (CalendarUnit::Hours, "h"),
+            (CalendarUnit::Minutes, "m"),
+            (CalendarUnit::Seconds, "s"),
+        ];
+        let (start, end) = match self <= now {
+            true => (self, now),
+            false => (now, self),
+        };
+        for (unit, suffix) in UNITS {
+            let count = start.count_between(end, unit);
+            if count > 0 {
+                return format!("{}{}", count, suffix);
+            }
+        }
+        String::from("0s")
+    }
+
+    /// Returns the midnight of the 1st of each month within `[start, end)`, in order.
+    /// `start` is included when it is exactly midnight of a 1st, while `end` is always excluded.
+    ///
+    /// Example:
+    /// ```
+    /// use stoicdreams_timestamp::prelude::*;
+    ///
+    /// let start = DateTime::new(2024, 1, 15, 0, 0, 0);
+    /// let end = DateTime::new(2024, 3, 15, 0, 0, 0);
+    /// let months: Vec<String> = DateTime::months_between(&start, &end).map(|month| month.format()).collect();
+    /// assert_eq!(months, vec!["2024-02-01 00:00:00.000", "2024-03-01 00:00:00.000"]);
+    /// ```
+    pub fn months_between(start: &DateTime, end: &DateTime) -> impl Iterator<Item = DateTime> {
+        let (year, month) = (start.get_year(), start.get_month());
+        let first = Self::new(year, month, 1, 0, 0, 0);
+        let first = match first < *start {
+            true => first.start_of_next_month(),
+            false => Some(first),
+        };
+        let end = end.clone();
+        std::iter::successors(first, Self::start_of_next_month)
+            .take_while(move |month| *month < end)
+    }
+
+    /// Returns each `DateTime` from `start` through `end`, moving forward by `step` each time.
+    ///
+    /// `end` is included when it lands exactly on a step. When `end` is not step-aligned, the last value is the final
+    /// step before `end`, and `end` itself is excluded. A zero `step` yields only `start`.
+    ///
+    /// Example:
+    /// ```
+    /// use stoicdreams_timestamp::prelude::*;
+    ///
+    /// let start = DateTime::new(2024, 1, 1, 9, 0, 0);
+    /// let end = DateTime::new(2024, 1, 1, 10, 0, 0);
+    /// let times: Vec<String> = DateTime::range_inclusive(&start, &end, Time::from_minutes(30))
+    ///     .map(|time| time.format_cow("%H:%M").into_owned())
+    ///     .collect();
+    /// assert_eq!(times, vec!["09:00", "09:30", "10:00"]);
+    /// ```
+    pub fn range_inclusive(
+        start: &DateTime,
+        end: &DateTime,
+        step: Time,
+    ) -> impl Iterator<Item = DateTime> {
+        let step = DateTimeUnit::try_from(step.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+        let end = end.clone();
+        std::iter::successors(Some(start.clone()), move |current| match step {
+            0 => None,
+            _ => current
+                .milliseconds
+                .checked_add(step)
+                .map(Self::from_milliseconds),
+        })
+        .take_while(move |current| *current <= end)
+    }
+
+    /// Returns the number of whole 7-day weeks from `start` until `end`, which is negative when `end` is before `start`.
+    /// Weeks are a flat 7 days counted from `start`, regardless of calendar weeks.
+    pub fn weeks_between(start: &DateTime, end: &DateTime) -> i64 {
+        (end.milliseconds - start.milliseconds) / (7 * 24 * 60 * 60 * 1000)
+    }
+
+    /// Returns the midnight of each Monday that starts an ISO week within `[start, end)`, in order.
+    /// `start` is included when it is exactly midnight of a Monday, while `end` is always excluded.
+    ///
+    /// Example:
+    /// ```
+    /// use stoicdreams_timestamp::prelude::*;
+    ///
+    /// let start = DateTime::new(2024, 1, 3, 0, 0, 0);
+    /// let end = DateTime::new(2024, 1, 22, 0, 0, 0);
+    /// let weeks: Vec<String> = DateTime::iso_week_starts_between(&start, &end).map(|monday| monday.format()).collect();
+    /// assert_eq!(weeks, vec!["2024-01-08 00:00:00.000", "2024-01-15 00:00:00.000"]);
+    /// ```
+    pub fn iso_week_starts_between(
+        start: &DateTime,
+        end: &DateTime,
+    ) -> impl Iterator<Item = DateTime> {
+        let mut days = start.to_days();
+        if start.milliseconds % (24 * 60 * 60 * 1000) > 0 {
+            days += 1;
+        }
+        // Day 0 is a Sunday, so Mondays are the days 1 past a multiple of 7.
+        let days = days + (8 - days % 7) % 7;
+        let first = Self::from_milliseconds(days * 24 * 60 * 60 * 1000);
+        let end = end.clone();
+        std::iter::successors(Some(first), |monday| Some(monday.add_days(7)))
+            .take_while(move |monday| *monday < end)
+    }
+
+    /// Returns midnight of the 1st of the month after this `DateTime`, or `None` after December 9999.
+    fn start_of_next_month(&self) -> Option<DateTime> {
+        match self.get_month() {
+            12 => Self::try_new(self.get_year() + 1, 1, 1, 0, 0, 0).ok(),
+            month => Some(Self::new(self.get_year(), month + 1, 1, 0, 0, 0)),
+        }
+    }
+
+    /// Returns the number of whole calendar months from this `DateTime` until a later `other`.
+    fn months_until(&self, other: &DateTime) -> i64 {
+        let mut months = (other.get_year() as i64 - self.get_year() as i64) * 12
+            + (other.get_month() as i64 - self.get_month() as i64);
+        let start_of_month = (self.get_day_of_month(), self.milliseconds_of_day());
+        let end_of_month = (other.get_day_of_month(), other.milliseconds_of_day());
+        if end_of_month < start_of_month {
+            months -= 1;
+        }
+        months
+    }
+
+    /// Returns the number of milliseconds that have passed since midnight.
+    fn milliseconds_of_day(&self) -> DateTimeUnit {
+        self.milliseconds % (24 * 60 * 60 * 1000)
+    }
+
+    /// Returns the start of the `bucket` sized window containing this `DateTime`.
+    ///
+    /// Buckets are aligned to the Unix epoch (1970-01-01 00:00:00), not to midnight or AD 0,
+    /// so a 10 minute bucket always starts on a multiple of 10 minutes since the Unix epoch.
+    /// A zero length `bucket` returns this `DateTime` unchanged.
+    pub fn bucket_start(&self, bucket: Time) -> DateTime {
+        let bucket = DateTimeUnit::try_from(bucket.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+        if bucket == 0 {
+            return self.clone();
+        }
+        let unix = self.to_unix_epoch_milliseconds();
+        let start = unix - unix.rem_euclid(bucket);
+        Self {
+            milliseconds: start.saturating_add(EPOCH_START).max(0),
+        }
+    }
+
+    /// Returns this `DateTime` truncated down to a multiple of `unit`, aligned to the Unix epoch.
+    /// This is the same as `bucket_start(unit)`.
+    ///
+    /// Flooring from a single origin keeps buckets globally consistent: they run on across midnight instead of restarting
+    /// each day. For units that divide a day evenly (such as 90 minutes, 16 to a day) this matches flooring from midnight,
+    /// while other units (such as 7 hours) can have a bucket that starts on one day and ends on the next.
+    pub fn truncate_to(&self, unit: Time) -> DateTime {
+        self.bucket_start(unit)
+    }
+
+    /// Create a `WallStopwatch` that measures the wall-clock time passed since this `DateTime`.
+    pub fn stopwatch(&self) -> WallStopwatch {
+        WallStopwatch::new(self.clone())
+    }
+
+    /// Returns a key that sorts in chronological order, for use with `sort_by_key()` on structs holding a `DateTime`.
+    pub fn sort_key(&self) -> DateTimeUnit {
+        self.milliseconds
+    }
+
+    /// Returns this `DateTime` as a Windows `FILETIME` value, counting 100 nanosecond intervals since 1601-01-01 UTC.
+    /// Dates before 1601 are saturated to 0.
+    pub fn to_filetime(&self) -> u64 {
+        let milliseconds = (self.milliseconds - FILETIME_EPOCH_START).max(0) as u64;
+        milliseconds.saturating_mul(10_000)
+    }
+
+    /// Returns this `DateTime` as an Excel serial date, where the whole part counts days and the fraction is the time of day.
+    /// Dates before 1900-03-01 are shifted by a day to match Excel's phantom 1900-02-29 (see `from_excel_serial()`).
+    pub fn to_excel_serial(&self) -> f64 {
+        const DAY: f64 = (24 * 60 * 60 * 1000) as f64;
+        let serial = (self.milliseconds - EXCEL_EPOCH_START) as f64 / DAY;
+        if serial < 61.0 {
+            return serial - 1.0;
+        }
+        serial
+    }
+
+    /// Returns this `DateTime` as .NET `DateTime.Ticks`, counting 100 nanosecond intervals since 0001-01-01 UTC.
+    /// Dates in year 0 are before the .NET epoch and return negative ticks.
+    pub fn to_dotnet_ticks(&self) -> i64 {
+        (self.milliseconds - DOTNET_EPOCH_START).saturating_mul(10_000)
+    }
+
+    /// Returns this `DateTime` as a `PreciseDateTime`, which is lossless as milliseconds convert exactly to nanoseconds.
+    pub fn to_precise(&self) -> PreciseDateTime {
+        PreciseDateTime::from_nanoseconds(self.milliseconds as PreciseTimeUnit * 1_000_000)
+    }
+
+    /// Returns the number of whole days since the Unix epoch, as commonly used to partition data by day.
+    /// Days before the Unix epoch are negative, so 1969-12-31 is day -1.
+    pub fn epoch_day(&self) -> i64 {
+        self.to_unix_epoch_milliseconds()
+            .div_euclid(24 * 60 * 60 * 1000)
+    }
+
+    /// Returns the total number of milliseconds since the Unix epoch, typed so it cannot be mistaken for `AdMillis`.
+    pub fn to_unix_millis(&self) -> UnixMillis {
+        UnixMillis(self.to_unix_epoch_milliseconds())
+    }
+
+    /// Returns the total number of milliseconds since 0 AD, typed so it cannot be mistaken for `UnixMillis`.
+    pub fn to_ad_millis(&self) -> AdMillis {
+        AdMillis(self.milliseconds)
+    }
+
+    /// Returns the total number of milliseconds since the Unix epoch.
+    pub fn to_unix_epoch_milliseconds(&self) -> DateTimeUnit {
+        self.milliseconds - EPOCH_START
+    }
+
+    /// Returns the fractional number of seconds since the Unix epoch (e.g. `1685284606.076`).
+    /// See `from_unix_epoch_seconds_f64()` for the precision limits of `f64`.
+    pub fn to_unix_epoch_seconds_f64(&self) -> f64 {
+        self.to_unix_epoch_milliseconds() as f64 / 1000.0
+    }
+
+    /// Returns the total number of milliseconds since 0 AD.
+    pub fn to_milliseconds(&self) -> DateTimeUnit {
+        self.milliseconds
+    }
+
+    /// Returns the total number of seconds since 0 AD.
+    pub fn to_seconds(&self) -> DateTimeUnit {
+        self.milliseconds / 1000
+    }
+
+    /// Returns the total number of minutes since 0 AD.
+    pub fn to_minutes(&self) -> DateTimeUnit {
+        self.milliseconds / (60 * 1000)
+    }
+
+    /// Returns the total number of hours since 0 AD.
+    pub fn to_hours(&self) -> DateTimeUnit {
+        self.milliseconds / (60 * 60 * 1000)
+    }
+
+    /// Returns the total number of whole days since the start of 0 AD, which is 0 on Jan 1st of 0 AD.
+    pub fn to_days(&self) -> DateTimeUnit {
+        self.milliseconds / (24 * 60 * 60 * 1000)
+    }
+}
+
+/// The number of days in a common year before the start of each month, followed by the length of the year.
+const COMMON_YEAR_MONTH_STARTS: [u16; 13] =
+    [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334, 365];
+
+/// The number of days in a leap year before the start of each month, followed by the length of the year.
+const LEAP_YEAR_MONTH_STARTS: [u16; 13] =
+    [0, 31, 60, 91, 121, 152, 182, 213, 244, 274, 305, 335, 366];
+
+/// Returns the year and the ordinal day of the year (starting at 1) for whole `days` since the start of AD 0.
+fn year_and_day_of_year(days: DateTimeUnit) -> (u16, u16) {
+    const DAYS_IN_400_YEARS: DateTimeUnit = 146_097;
+    const DAYS_IN_100_YEARS: DateTimeUnit = 36_524;
+    const DAYS_IN_4_YEARS: DateTimeUnit = 1_461;
+    if days < 365 {
+        return (0, days as u16 + 1);
+    }
+    // Days since Jan 1st of AD 1, split into whole 400, 100, 4, and 1 year cycles.
+    let days = days - 365;
+    let cycles_400 = days / DAYS_IN_400_YEARS;
+    let days = days % DAYS_IN_400_YEARS;
+    let cycles_100 = (days / DAYS_IN_100_YEARS).min(3);
+    let days = days - cycles_100 * DAYS_IN_100_YEARS;
+    let cycles_4 = days / DAYS_IN_4_YEARS;
+    let days = days % DAYS_IN_4_YEARS;
+    let years = (days / 365).min(3);
+    let days = days - years * 365;
+    let year = 1 + cycles_400 * 400 + cycles_100 * 100 + cycles_4 * 4 + years;
+    (year as u16, days as u16 + 1)
+}
+
+/// Splits an ordinal day of the year (starting at 1) into the month and day of the month.
+fn month_and_day(year: u16, day_of_year: u16) -> (u8, u16) {
+    let month_starts = match has_leap_day(year) {
+        true => &LEAP_YEAR_MONTH_STARTS,
+        false => &COMMON_YEAR_MONTH_STARTS,
+    };
+    let month = month_starts[1..12]
+        .iter()
+        .take_while(|start| **start < day_of_year)
+        .count();
+    (month as u8 + 1, day_of_year - month_starts[month])
+}
+
+/// Confirms the year (up to 9999, see `DateTime::MAX`), month, day, and time of day values are within their valid ranges.
+fn check_ranges(
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minutes: u8,
+    seconds: u8,
+) -> Result<(), TimeError> {
+    if year > 9999 {
+        return Err(TimeError::OutOfRange {
+            field: "year",
+            value: year as i128,
+        });
+    }
+    let checks = [
+        ("month", month, 1, 12),
+        ("day", day, 1, days_in_month(year, month)),
+        ("hour", hour, 0, 23),
+        ("minute", minutes, 0, 59),
+        ("second", seconds, 0, 59),
+    ];
+    for (field, value, min, max) in checks {
+        if value < min || value > max {
+            return Err(TimeError::OutOfRange {
+                field,
+                value: value as i128,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Clamps a leap second (a seconds value of 60) to the last millisecond of the preceding second, as leap seconds are not representable.
+fn clamp_leap_second(seconds: u32, milliseconds: u32) -> (u32, u32) {
+    match seconds {
+        60 => (59, 999),
+        _ => (seconds, milliseconds),
+    }
+}
+
+/// Clamps a `day` past the end of its month (up to 31) to the last day of the month.
+/// Other invalid values are returned as is so they are still reported as out of range.
+fn clamp_day_of_month(year: u32, month: u32, day: u32) -> u32 {
+    if year > 9999 || !(1..=12).contains(&month) || day > 31 {
+        return day;
+    }
+    day.min(days_in_month(year as u16, month as u8) as u32)
+}
+
+/// Parses a UTC offset of `Z`, `z`, `+HH:MM`, or `-HH:MM`.
+fn parse_utc_offset(parser: &mut Parser) -> Result<TimeZoneOffset, ParseError> {
+    if parser.optional_byte(b'Z') || parser.optional_byte(b'z') {
+        return Ok(TimeZoneOffset::utc());
+    }
+    let sign = match parser.peek() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        Some(_) => return Err(parser.unexpected("'Z' or UTC offset")),
+        None => return Err(parser.unexpected_end()),
+    };
+    parser.optional_byte(b'+');
+    parser.optional_byte(b'-');
+    let hours = parser.digits(2, "2 digit offset hour")?;
+    parser.expect_byte(b':', "':'")?;
+    let minutes = parser.digits(2, "2 digit offset minute")?;
+    if hours > 23 {
+        return Err(ParseError::OutOfRange {
+            field: "offset hour",
+            value: hours as i64,
+        });
+    }
+    if minutes > 59 {
+        return Err(ParseError::OutOfRange {
+            field: "offset minute",
+            value: minutes as i64,
+        });
+    }
+    Ok(TimeZoneOffset::from_minutes(
+        sign * (hours as i16 * 60 + minutes as i16),
+    ))
+}
+
+/// Parses a full or short English month name, returning the month number.
+fn parse_month_name(parser: &mut Parser) -> Result<u32, ParseError> {
+    let position = parser.position();
+    let name = parser.alphabetic("month name")?;
+    Month::from_name(name)
+        .map(|month| month.number() as u32)
+        .ok_or(ParseError::Unexpected {
+            position,
+            expected: "month name",
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datetime_formatting_from_new() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let utc_formatted = "2023-05-28 14:36:46.000";
+        assert_eq!(2023, datetime.get_year());
+        assert_eq!(5, datetime.get_month());
+        assert_eq!(28, datetime.get_day_of_month());
+        assert_eq!(14, datetime.get_hour_of_day());
+        assert_eq!(36, datetime.get_minutes_of_hour());
+        assert_eq!(46, datetime.get_seconds_of_minute());
+        assert_eq!(0, datetime.get_day_of_week());
+        assert_eq!(utc_formatted, datetime.format());
+    }
+
+    #[test]
+    fn test_datetime_formatting_pre_1970() {
+        let datetime = DateTime::new(1903, 12, 25, 18, 36, 46);
+        let utc_formatted = "1903-12-25 18:36:46.000";
+        assert_eq!(utc_formatted, datetime.format());
+        assert_eq!(1903, datetime.get_year());
+        assert_eq!(12, datetime.get_month());
+        assert_eq!(25, datetime.get_day_of_month());
+        assert_eq!(18, datetime.get_hour_of_day());
+        assert_eq!(36, datetime.get_minutes_of_hour());
+        assert_eq!(5, datetime.get_day_of_week());
+        assert_eq!(46, datetime.get_seconds_of_minute());
+    }
+
+    #[test]
+    fn test_datetime_formatting_from_milliseconds() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let utc_formatted = "2023-05-28 14:36:46.076";
+        assert_eq!(utc_formatted, datetime.format());
+        assert_eq!(2023, datetime.get_year());
+        assert_eq!(5, datetime.get_month());
+        assert_eq!(28, datetime.get_day_of_month());
         assert_eq!(14, datetime.get_hour_of_day());
         assert_eq!(36, datetime.get_minutes_of_hour());
         assert_eq!(0, datetime.get_day_of_week());
         assert_eq!(46, datetime.get_seconds_of_minute());
     }
 
+    #[test]
+    fn test_datetime_now_uses_unix_epoch() {
+        let datetime = DateTime::now();
+        assert!(datetime.get_year() >= 2023);
+        assert!(datetime.to_unix_epoch_milliseconds() > 1_685_284_606_076);
+    }
+
+    #[test]
+    fn test_datetime_deserialize_unix_milliseconds_number() {
+        let datetime: DateTime = serde_json::from_str("1685284606076").unwrap();
+        assert_eq!(1_685_284_606_076, datetime.to_unix_epoch_milliseconds());
+        assert_eq!("2023-05-28 14:36:46.076", datetime.format());
+    }
+
+    #[test]
+    fn test_datetime_deserialize_unix_milliseconds_string() {
+        let datetime: DateTime = serde_json::from_str("\"1685284606076\"").unwrap();
+        assert_eq!(1_685_284_606_076, datetime.to_unix_epoch_milliseconds());
+        assert_eq!("2023-05-28 14:36:46.076", datetime.format());
+    }
+
+    #[test]
+    fn test_datetime_deserialize_struct_form() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let json = serde_json::to_string(&datetime).unwrap();
+        assert_eq!(datetime, serde_json::from_str::<DateTime>(&json).unwrap());
+        let json = format!("{{\"milliseconds\":\"{}\"}}", datetime.to_milliseconds());
+        assert_eq!(datetime, serde_json::from_str::<DateTime>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_datetime_deserialize_rejects_non_numeric_string() {
+        assert!(serde_json::from_str::<DateTime>("\"yesterday\"").is_err());
+    }
+
+    #[test]
+    fn test_datetime_deserialize_out_of_range_is_error() {
+        let error = serde_json::from_str::<DateTime>("-99999999999999999").unwrap_err();
+        assert!(error.to_string().contains("milliseconds value"));
+        assert!(serde_json::from_str::<DateTime>("\"-99999999999999999\"").is_err());
+        assert!(serde_json::from_str::<DateTime>(r#"{"milliseconds":-1}"#).is_err());
+        assert!(serde_json::from_str::<DateTime>(&i64::MAX.to_string()).is_err());
+        let max = DateTime::MAX;
+        let last_unix = max.to_unix_epoch_milliseconds();
+        for json in [
+            String::from("9223309869721975807"),
+            String::from("\"9223309869721975807\""),
+            (last_unix + 1).to_string(),
+            format!(r#"{{"milliseconds":{}}}"#, i64::MAX),
+            format!(r#"{{"milliseconds":{}}}"#, max.to_milliseconds() + 1),
+        ] {
+            assert!(serde_json::from_str::<DateTime>(&json).is_err(), "{}", json);
+        }
+        assert_eq!(
+            max,
+            serde_json::from_str::<DateTime>(&last_unix.to_string()).unwrap()
+        );
+        assert_eq!(
+            max,
+            serde_json::from_str::<DateTime>(&serde_json::to_string(&max).unwrap()).unwrap()
+        );
+        serde_test::assert_de_tokens_error::<serde_test::Compact<DateTime>>(
+            &[
+                serde_test::Token::Seq { len: Some(1) },
+                serde_test::Token::I64(max.to_milliseconds() + 1),
+                serde_test::Token::SeqEnd,
+            ],
+            &format!(
+                "milliseconds value {} is out of range",
+                max.to_milliseconds() + 1
+            ),
+        );
+        assert_eq!(
+            Ok(DateTime::from_milliseconds(0)),
+            DateTime::try_from_unix_epoch_milliseconds(-EPOCH_START)
+        );
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "milliseconds",
+                value: (-EPOCH_START - 1) as i128
+            }),
+            DateTime::try_from_unix_epoch_milliseconds(-EPOCH_START - 1)
+        );
+    }
+
+    #[test]
+    fn test_datetime_count_between_each_unit() {
+        let start = DateTime::new(2019, 3, 15, 10, 20, 30);
+        let end = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(4, start.count_between(&end, CalendarUnit::Years));
+        assert_eq!(50, start.count_between(&end, CalendarUnit::Months));
+        assert_eq!(219, start.count_between(&end, CalendarUnit::Weeks));
+        assert_eq!(1535, start.count_between(&end, CalendarUnit::Days));
+        assert_eq!(36_844, start.count_between(&end, CalendarUnit::Hours));
+        assert_eq!(2_210_656, start.count_between(&end, CalendarUnit::Minutes));
+        assert_eq!(
+            132_639_376,
+            start.count_between(&end, CalendarUnit::Seconds)
+        );
+        assert_eq!(-50, end.count_between(&start, CalendarUnit::Months));
+        assert_eq!(-1535, end.count_between(&start, CalendarUnit::Days));
+    }
+
+    #[test]
+    fn test_datetime_count_between_partial_month() {
+        let start = DateTime::new(2023, 3, 15, 10, 0, 0);
+        assert_eq!(
+            0,
+            start.count_between(&DateTime::new(2023, 4, 15, 9, 59, 59), CalendarUnit::Months)
+        );
+        assert_eq!(
+            1,
+            start.count_between(&DateTime::new(2023, 4, 15, 10, 0, 0), CalendarUnit::Months)
+        );
+        assert_eq!(
+            0,
+            start.count_between(&DateTime::new(2024, 3, 14, 10, 0, 0), CalendarUnit::Years)
+        );
+        assert_eq!(
+            1,
+            start.count_between(&DateTime::new(2024, 3, 15, 10, 0, 0), CalendarUnit::Years)
+        );
+    }
+
+    #[test]
+    fn test_datetime_clamp_to_business_hours_on_saturday() {
+        let saturday = DateTime::new(2023, 5, 27, 11, 15, 0);
+        assert!(saturday.is_weekend());
+        let clamped = saturday.clamp_to_business_hours(9, 17);
+        assert_eq!(DateTime::new(2023, 5, 29, 9, 0, 0), clamped);
+        assert_eq!(1, clamped.get_day_of_week());
+    }
+
+    #[test]
+    fn test_datetime_clamp_to_business_hours_after_hours_weekday() {
+        let wednesday_evening = DateTime::new(2023, 5, 24, 20, 0, 0);
+        let clamped = wednesday_evening.clamp_to_business_hours(9, 17);
+        assert_eq!(DateTime::new(2023, 5, 25, 9, 0, 0), clamped);
+        let friday_evening = DateTime::new(2023, 5, 26, 20, 0, 0);
+        let clamped = friday_evening.clamp_to_business_hours(9, 17);
+        assert_eq!(DateTime::new(2023, 5, 29, 9, 0, 0), clamped);
+    }
+
+    #[test]
+    fn test_datetime_clamp_to_business_hours_weekday() {
+        let early = DateTime::new(2023, 5, 24, 7, 30, 0);
+        assert_eq!(
+            DateTime::new(2023, 5, 24, 9, 0, 0),
+            early.clamp_to_business_hours(9, 17)
+        );
+        let during = DateTime::new(2023, 5, 24, 16, 59, 59);
+        assert_eq!(during, during.clamp_to_business_hours(9, 17));
+    }
+
+    #[test]
+    fn test_datetime_default_is_epoch() {
+        assert_eq!(DateTime::epoch(), DateTime::default());
+        assert_eq!(DateTime::default(), DateTime::default());
+        assert_eq!(0, DateTime::default().to_unix_epoch_milliseconds());
+        assert_eq!("1970-01-01 00:00:00.000", DateTime::default().format());
+    }
+
+    #[test]
+    fn test_datetime_from_http_date() {
+        let expected = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let imf = DateTime::from_http_date("Sun, 28 May 2023 14:36:46 GMT").unwrap();
+        assert_eq!(expected, imf);
+        let rfc850 = DateTime::from_http_date("Sunday, 28-May-23 14:36:46 GMT").unwrap();
+        assert_eq!(expected, rfc850);
+        let asctime = DateTime::from_http_date("Sun May 28 14:36:46 2023").unwrap();
+        assert_eq!(expected, asctime);
+        let asctime = DateTime::from_http_date("Sun May  7 14:36:46 2023").unwrap();
+        assert_eq!(DateTime::new(2023, 5, 7, 14, 36, 46), asctime);
+        let year_0 = DateTime::new(0, 5, 28, 14, 36, 46);
+        let imf = DateTime::from_http_date("Sun, 28 May 0000 14:36:46 GMT").unwrap();
+        assert_eq!(year_0, imf);
+        let asctime = DateTime::from_http_date("Sun May 28 14:36:46 0000").unwrap();
+        assert_eq!(year_0, asctime);
+    }
+
+    #[test]
+    fn test_datetime_from_http_date_malformed() {
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 8,
+                expected: "month name",
+            }),
+            DateTime::from_http_date("Sun, 28 Mai 2023 14:36:46 GMT")
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 26,
+                expected: "GMT",
+            }),
+            DateTime::from_http_date("Sun, 28 May 2023 14:36:46 UTC")
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "day",
+                value: 31,
+            }),
+            DateTime::from_http_date("Wed, 31 Jun 2023 14:36:46 GMT")
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd { position: 16 }),
+            DateTime::from_http_date("Sun, 28 May 2023")
+        );
+        assert!(DateTime::from_http_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_datetime_to_http_date() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!("Sun, 28 May 2023 14:36:46 GMT", datetime.to_http_date());
+        let datetime = DateTime::new(2023, 6, 7, 8, 9, 5);
+        assert_eq!("Wed, 07 Jun 2023 08:09:05 GMT", datetime.to_http_date());
+        assert_eq!(
+            datetime,
+            DateTime::from_http_date(&datetime.to_http_date()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_datetime_from_local_offset() {
+        let offset = TimeZoneOffset::from_hours(2);
+        let utc = DateTime::from_local(2023, 5, 28, 16, 36, 46, offset);
+        assert_eq!(DateTime::new(2023, 5, 28, 14, 36, 46), utc);
+        assert_eq!(14, utc.get_hour_of_day());
+        let local = utc.to_local(offset);
+        assert_eq!(DateTime::new(2023, 5, 28, 16, 36, 46), local);
+        assert_eq!("2023-05-28 16:36:46.000", local.format());
+    }
+
+    #[test]
+    fn test_datetime_from_local_negative_offset_crosses_day() {
+        let offset = TimeZoneOffset::from_minutes(-570);
+        let utc = DateTime::from_local(2023, 5, 28, 20, 0, 0, offset);
+        assert_eq!(DateTime::new(2023, 5, 29, 5, 30, 0), utc);
+        assert_eq!(DateTime::new(2023, 5, 28, 20, 0, 0), utc.to_local(offset));
+    }
+
+    #[test]
+    fn test_datetime_in_half_open() {
+        let start = DateTime::new(2023, 5, 28, 14, 0, 0);
+        let end = DateTime::new(2023, 5, 28, 15, 0, 0);
+        assert!(start.in_half_open(&start, &end));
+        assert!(!end.in_half_open(&start, &end));
+        assert!(DateTime::new(2023, 5, 28, 14, 59, 59).in_half_open(&start, &end));
+        assert!(!DateTime::new(2023, 5, 28, 13, 59, 59).in_half_open(&start, &end));
+        assert!(!start.in_half_open(&start, &start));
+    }
+
+    #[test]
+    fn test_datetime_parse_with_format() {
+        let datetime = DateTime::parse("2023-05-28 14:36:46.076", "%Y-%m-%d %H:%M:%S.%f").unwrap();
+        assert_eq!(1_685_284_606_076, datetime.to_unix_epoch_milliseconds());
+        let datetime = DateTime::parse("28/05/2023", "%d/%m/%Y").unwrap();
+        assert_eq!(DateTime::new(2023, 5, 28, 0, 0, 0), datetime);
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 4,
+                expected: "literal text",
+            }),
+            DateTime::parse("2023/05/28", "%Y-%m-%d")
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "month",
+                value: 13,
+            }),
+            DateTime::parse("2023-13-28", "%Y-%m-%d")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingInput { position: 10 }),
+            DateTime::parse("2023-05-28 14:36", "%Y-%m-%d")
+        );
+    }
+
+    #[test]
+    fn test_datetime_from_rfc3339() {
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(
+            expected,
+            DateTime::from_rfc3339("2023-05-28T14:36:46.076Z").unwrap()
+        );
+        assert_eq!(
+            expected,
+            DateTime::from_rfc3339("2023-05-28T16:36:46.076+02:00").unwrap()
+        );
+        assert_eq!(
+            expected,
+            DateTime::from_rfc3339("2023-05-28T06:36:46.076-08:00").unwrap()
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 14, 36, 46),
+            DateTime::from_rfc3339("2023-05-28T14:36:46Z").unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd { position: 19 }),
+            DateTime::from_rfc3339("2023-05-28T14:36:46")
+        );
+    }
+
+    #[test]
+    fn test_datetime_parse_flexible() {
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            DateTime::parse_flexible("2023-05-28T14:36:46.076Z").unwrap()
+        );
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            DateTime::parse_flexible("2023-05-28 14:36:46.076").unwrap()
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 0, 0, 0),
+            DateTime::parse_flexible("2023-05-28").unwrap()
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 14, 36, 0),
+            DateTime::parse_flexible("2023-05-28 14:36").unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 0,
+                expected: "4 digit year",
+            }),
+            DateTime::parse_flexible("not a date")
+        );
+    }
+
+    #[test]
+    fn test_datetime_next_daily_later_today() {
+        let now = DateTime::new(2023, 5, 28, 8, 15, 30);
+        assert_eq!(DateTime::new(2023, 5, 28, 9, 0, 0), now.next_daily(9, 0));
+        let now = DateTime::new(2023, 5, 28, 9, 0, 0);
+        assert_eq!(now, now.next_daily(9, 0));
+    }
+
+    #[test]
+    fn test_datetime_next_daily_rolls_to_tomorrow() {
+        let now = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(DateTime::new(2023, 5, 29, 9, 0, 0), now.next_daily(9, 0));
+        assert_eq!(
+            DateTime::new(2023, 5, 29, 14, 36, 0),
+            now.next_daily(14, 36)
+        );
+    }
+
+    #[test]
+    fn test_datetime_add_precise_truncating() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let moved = datetime.add_precise_truncating(&PreciseTime::from_nanoseconds(1_500_000));
+        assert_eq!(1, moved.to_milliseconds() - datetime.to_milliseconds());
+        let moved = datetime.add_precise_truncating(&PreciseTime::from_nanoseconds(999_999));
+        assert_eq!(datetime, moved);
+    }
+
+    #[test]
+    fn test_datetime_sort_key() {
+        struct Record {
+            name: &'static str,
+            created: DateTime,
+        }
+        let mut records = [
+            Record {
+                name: "b",
+                created: DateTime::new(2023, 5, 28, 14, 36, 46),
+            },
+            Record {
+                name: "c",
+                created: DateTime::new(2024, 2, 14, 8, 0, 0),
+            },
+            Record {
+                name: "a",
+                created: DateTime::new(1999, 7, 4, 12, 0, 0),
+            },
+        ];
+        records.sort_by_key(|record| record.created.sort_key());
+        let names: Vec<&str> = records.iter().map(|record| record.name).collect();
+        assert_eq!(vec!["a", "b", "c"], names);
+        assert!(records[0].created < records[1].created);
+    }
+
+    #[test]
+    fn test_datetime_year_end_of_ad_zero() {
+        let datetime = DateTime::new(0, 12, 31, 23, 59, 59);
+        assert_eq!(0, datetime.get_year());
+        assert_eq!(12, datetime.get_month());
+        assert_eq!(31, datetime.get_day_of_month());
+        assert_eq!(365, datetime.get_day_of_year());
+        assert_eq!("0000-12-31 23:59:59.000", datetime.format());
+        let datetime = DateTime::new(1, 1, 1, 0, 0, 0);
+        assert_eq!(1, datetime.get_year());
+        assert_eq!(1, datetime.get_month());
+        assert_eq!(1, datetime.get_day_of_month());
+        assert_eq!(1, datetime.get_day_of_year());
+    }
+
+    #[test]
+    fn test_datetime_year_end_of_leap_year() {
+        let datetime = DateTime::new(2020, 12, 31, 23, 59, 59);
+        assert_eq!(2020, datetime.get_year());
+        assert_eq!(12, datetime.get_month());
+        assert_eq!(31, datetime.get_day_of_month());
+        assert_eq!(366, datetime.get_day_of_year());
+        assert_eq!("2020-12-31 23:59:59.000", datetime.format());
+        let datetime = DateTime::new(2021, 1, 1, 0, 0, 0);
+        assert_eq!("2021-01-01 00:00:00.000", datetime.format());
+        assert_eq!(1, datetime.get_day_of_year());
+        let datetime = DateTime::new(2021, 12, 31, 0, 0, 0);
+        assert_eq!("2021-12-31 00:00:00.000", datetime.format());
+        assert_eq!(365, datetime.get_day_of_year());
+    }
+
+    #[test]
+    fn test_datetime_month_boundaries() {
+        assert_eq!(
+            "2020-02-29 00:00:00.000",
+            DateTime::new(2020, 2, 29, 0, 0, 0).format()
+        );
+        assert_eq!(
+            "2020-03-01 00:00:00.000",
+            DateTime::new(2020, 3, 1, 0, 0, 0).format()
+        );
+        assert_eq!(
+            "2023-03-01 00:00:00.000",
+            DateTime::new(2023, 3, 1, 0, 0, 0).format()
+        );
+        assert_eq!(
+            "2023-01-31 00:00:00.000",
+            DateTime::new(2023, 1, 31, 0, 0, 0).format()
+        );
+        assert_eq!(
+            "2023-02-01 00:00:00.000",
+            DateTime::new(2023, 2, 1, 0, 0, 0).format()
+        );
+    }
+
+    #[test]
+    fn test_datetime_new_round_trips_every_day() {
+        let years = (0..=8)
+            .chain(395..=405)
+            .chain(1895..=1905)
+            .chain(1995..=2105);
+        for year in years {
+            let mut day_of_year = 0;
+            for month in 1..=12 {
+                for day in 1..=days_in_month(year, month) {
+                    day_of_year += 1;
+                    let datetime = DateTime::new(year, month, day, 12, 0, 0);
+                    assert_eq!(year, datetime.get_year());
+                    assert_eq!(month, datetime.get_month());
+                    assert_eq!(day as u16, datetime.get_day_of_month());
+                    assert_eq!(day_of_year, datetime.get_day_of_year());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_datetime_from_ordinal() {
+        let datetime = DateTime::from_ordinal(2020, 60).unwrap();
+        assert_eq!(DateTime::new(2020, 2, 29, 0, 0, 0), datetime);
+        assert_eq!(60, datetime.get_day_of_year());
+        let datetime = DateTime::from_ordinal(2021, 60).unwrap();
+        assert_eq!(DateTime::new(2021, 3, 1, 0, 0, 0), datetime);
+        assert_eq!(
+            DateTime::new(2020, 12, 31, 0, 0, 0),
+            DateTime::from_ordinal(2020, 366).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_datetime_from_ordinal_out_of_range() {
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "day_of_year",
+                value: 366,
+            }),
+            DateTime::from_ordinal(2021, 366)
+        );
+        assert!(DateTime::from_ordinal(2021, 0).is_err());
+    }
+
+    #[test]
+    fn test_datetime_parse_auto_width_fraction() {
+        let format = "%Y-%m-%dT%H:%M:%S%.fZ";
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(
+            datetime,
+            DateTime::parse("2023-05-28T14:36:46Z", format).unwrap()
+        );
+        assert_eq!(
+            datetime.to_milliseconds() + 500,
+            DateTime::parse("2023-05-28T14:36:46.5Z", format)
+                .unwrap()
+                .to_milliseconds()
+        );
+    }
+
+    #[test]
+    fn test_datetime_bucket_start_ten_minutes() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let bucket = datetime.bucket_start(Time::from_minutes(10));
+        assert_eq!("2023-05-28 14:30:00.000", bucket.format());
+        assert_eq!(0, bucket.to_unix_epoch_milliseconds() % 600_000);
+        assert_eq!(bucket, bucket.bucket_start(Time::from_minutes(10)));
+        assert_eq!(bucket, datetime.truncate_to(Time::from_minutes(10)));
+        assert_eq!(datetime, datetime.bucket_start(Time::from_milliseconds(0)));
+    }
+
+    #[test]
+    fn test_datetime_bucket_start_aligned_to_unix_epoch() {
+        // 7 minutes does not divide a day, so epoch alignment differs from midnight alignment.
+        let datetime = DateTime::new(2023, 5, 28, 0, 3, 0);
+        let bucket = datetime.bucket_start(Time::from_minutes(7));
+        assert_eq!(0, bucket.to_unix_epoch_milliseconds() % 420_000);
+        assert!(bucket <= datetime);
+        assert!(datetime.to_milliseconds() - bucket.to_milliseconds() < 420_000);
+        let before_epoch = DateTime::new(1969, 12, 31, 23, 55, 0);
+        assert_eq!(
+            "1969-12-31 23:50:00.000",
+            before_epoch.bucket_start(Time::from_minutes(10)).format()
+        );
+    }
+
+    #[test]
+    fn test_datetime_try_from_integers() {
+        let datetime = DateTime::try_from(63_852_417_406_076_i64).unwrap();
+        assert_eq!(63_852_417_406_076, datetime.to_milliseconds());
+        let datetime: DateTime = 63_852_417_406_076_u64.try_into().unwrap();
+        assert_eq!("2023-05-28 14:36:46.076", datetime.format());
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "milliseconds",
+                value: -1
+            }),
+            DateTime::try_from(-1_i64)
+        );
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "milliseconds",
+                value: u64::MAX as i128
+            }),
+            DateTime::try_from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_datetime_fields_match_getters() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let fields = datetime.fields();
+        assert_eq!(
+            DateFields {
+                year: 2023,
+                month: 5,
+                day: 28,
+                hour: 14,
+                minute: 36,
+                second: 46,
+                millisecond: 76,
+                day_of_week: 0,
+                day_of_year: 148,
+            },
+            fields
+        );
+        for datetime in [
+            DateTime::new(2020, 12, 31, 23, 59, 59),
+            DateTime::new(2024, 2, 29, 0, 0, 0),
+            DateTime::new(0, 1, 1, 0, 0, 0),
+        ] {
+            let fields = datetime.fields();
+            assert_eq!(datetime.get_year(), fields.year);
+            assert_eq!(datetime.get_month(), fields.month);
+            assert_eq!(datetime.get_day_of_month(), fields.day);
+            assert_eq!(datetime.get_day_of_year(), fields.day_of_year);
+            assert_eq!(datetime.get_day_of_week(), fields.day_of_week);
+        }
+    }
+
+    #[test]
+    fn test_datetime_first_and_last_day_of_month() {
+        let new_year = DateTime::new(2023, 1, 1, 0, 0, 0);
+        assert!(new_year.is_first_day_of_month());
+        assert!(!new_year.is_last_day_of_month());
+        let common_february = DateTime::new(2023, 2, 28, 23, 59, 59);
+        assert!(common_february.is_last_day_of_month());
+        assert!(!common_february.is_first_day_of_month());
+        assert!(!DateTime::new(2024, 2, 28, 0, 0, 0).is_last_day_of_month());
+        assert!(DateTime::new(2024, 2, 29, 0, 0, 0).is_last_day_of_month());
+        assert!(DateTime::new(2023, 12, 31, 12, 0, 0).is_last_day_of_month());
+        assert!(!DateTime::new(2023, 12, 30, 12, 0, 0).is_last_day_of_month());
+    }
+
+    #[test]
+    fn test_datetime_from_ym_with_month() {
+        let datetime = DateTime::from_ym(2023, Month::May, 28);
+        assert_eq!(DateTime::new(2023, 5, 28, 0, 0, 0), datetime);
+        assert_eq!("2023-05-28 00:00:00.000", datetime.format());
+        assert_eq!(
+            "2024-12-31 00:00:00.000",
+            DateTime::from_ym(2024, Month::December, 31).format()
+        );
+    }
+
+    #[test]
+    fn test_datetime_month_boundaries_in_common_and_leap_years() {
+        for year in [2023, 2024, 1900, 2000] {
+            for month in 1..=12 {
+                let first = DateTime::new(year, month, 1, 0, 0, 0);
+                assert_eq!(month, first.get_month(), "{}-{} first", year, month);
+                assert_eq!(1, first.get_day_of_month(), "{}-{} first", year, month);
+                let last_day = days_in_month(year, month);
+                let last = DateTime::new(year, month, last_day, 23, 59, 59);
+                assert_eq!(month, last.get_month(), "{}-{} last", year, month);
+                assert_eq!(
+                    last_day as u16,
+                    last.get_day_of_month(),
+                    "{}-{} last",
+                    year,
+                    month
+                );
+            }
+        }
+        assert_eq!(3, DateTime::new(2023, 3, 1, 0, 0, 0).get_month());
+        assert_eq!(2, DateTime::new(2024, 2, 29, 0, 0, 0).get_month());
+        assert_eq!(29, DateTime::new(2024, 2, 29, 0, 0, 0).get_day_of_month());
+    }
+
+    #[test]
+    fn test_datetime_unix_weekday_matches_date_command() {
+        // Reference values from `date -d <date> +%u`.
+        let references = [
+            ((1970, 1, 1), 4),
+            ((2000, 1, 1), 6),
+            ((2023, 5, 28), 7),
+            ((2023, 5, 29), 1),
+            ((2024, 2, 29), 4),
+            ((1900, 1, 1), 1),
+        ];
+        for ((year, month, day), expected) in references {
+            let datetime = DateTime::new(year, month, day, 12, 0, 0);
+            assert_eq!(
+                expected,
+                datetime.unix_weekday(),
+                "{}-{}-{}",
+                year,
+                month,
+                day
+            );
+            assert_eq!(
+                expected.to_string(),
+                time_format(datetime.to_milliseconds(), "%u")
+            );
+        }
+    }
+
+    #[test]
+    fn test_datetime_checked_add_days() {
+        let datetime = DateTime::new(0, 1, 10, 12, 0, 0);
+        assert_eq!(
+            Some(DateTime::new(0, 1, 1, 12, 0, 0)),
+            datetime.checked_add_days(-9)
+        );
+        assert_eq!(None, datetime.checked_add_days(-10));
+        assert_eq!(None, datetime.checked_add_days(-1_000_000));
+        assert_eq!(None, datetime.checked_add_days(i64::MAX));
+        assert_eq!(None, datetime.checked_add_days(i64::MIN));
+        assert_eq!(
+            Some(DateTime::new(2024, 3, 1, 0, 0, 0)),
+            DateTime::new(2024, 2, 28, 0, 0, 0).checked_add_days(2)
+        );
+        assert_eq!(DateTime::from_milliseconds(0), datetime.add_days(-10));
+    }
+
+    #[test]
+    fn test_datetime_julian_calendar_dates() {
+        let reform = DateTime::new(1582, 10, 15, 12, 0, 0);
+        assert_eq!((1582, 10, 5), reform.to_julian_calendar_date());
+        assert_eq!(
+            "1582-10-05 12:00:00.000",
+            reform.format_in_calendar(CalendarSystem::Julian)
+        );
+        assert_eq!(
+            reform.format(),
+            reform.format_in_calendar(CalendarSystem::default())
+        );
+        let before_reform = DateTime::new(1582, 10, 4, 0, 0, 0);
+        assert_eq!(
+            (1582, 10, 4),
+            before_reform.calendar_date(CalendarSystem::Gregorian)
+        );
+        assert_eq!(
+            (1582, 9, 24),
+            before_reform.calendar_date(CalendarSystem::Julian)
+        );
+        assert_eq!(
+            (1999, 12, 19),
+            DateTime::new(2000, 1, 1, 0, 0, 0).to_julian_calendar_date()
+        );
+        assert_eq!(
+            (1, 1, 3),
+            DateTime::new(1, 1, 1, 0, 0, 0).to_julian_calendar_date()
+        );
+        assert_eq!(
+            (1000, 2, 28),
+            DateTime::new(1000, 3, 5, 0, 0, 0).to_julian_calendar_date()
+        );
+    }
+
+    #[test]
+    fn test_datetime_until_next_boundaries() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(
+            "14:36:46.076",
+            time_format(datetime.to_milliseconds(), "%H:%M:%S.%f")
+        );
+        let until_hour = datetime.until_next_hour();
+        assert_eq!(23, until_hour.get_minutes_of_hour());
+        assert_eq!(13, until_hour.get_seconds_of_minute());
+        assert_eq!(924, until_hour.get_milliseconds_of_second());
+        assert_eq!(13_924, datetime.until_next_minute().to_milliseconds());
+        assert_eq!("09:23:13.924", datetime.until_next_day().format());
+        let midnight = DateTime::new(2023, 5, 28, 0, 0, 0);
+        assert_eq!(Time::from_days(1), midnight.until_next_day());
+        assert_eq!(Time::from_hours(1), midnight.until_next_hour());
+        assert_eq!(Time::from_minutes(1), midnight.until_next_minute());
+    }
+
+    #[test]
+    fn test_datetime_from_rfc3339_malformed() {
+        let cases = [
+            ("", ParseError::UnexpectedEnd { position: 0 }),
+            (
+                "2023/05-28T14:36:46Z",
+                ParseError::Unexpected {
+                    position: 4,
+                    expected: "'-'",
+                },
+            ),
+            (
+                "2023-5-28T14:36:46Z",
+                ParseError::Unexpected {
+                    position: 6,
+                    expected: "2 digit month",
+                },
+            ),
+            (
+                "2023-05-28X14:36:46Z",
+                ParseError::Unexpected {
+                    position: 10,
+                    expected: "'T'",
+                },
+            ),
+            (
+                "2023-05-28T14:36:46.Z",
+                ParseError::Unexpected {
+                    position: 20,
+                    expected: "fraction of a second",
+                },
+            ),
+            (
+                "2023-13-28T14:36:46Z",
+                ParseError::OutOfRange {
+                    field: "month",
+                    value: 13,
+                },
+            ),
+            (
+                "2023-05-28T24:36:46Z",
+                ParseError::OutOfRange {
+                    field: "hour",
+                    value: 24,
+                },
+            ),
+            (
+                "2023-05-28T14:36:46Zé",
+                ParseError::TrailingInput { position: 20 },
+            ),
+            (
+                "2023-05-2éT14:36:46Z",
+                ParseError::Unexpected {
+                    position: 9,
+                    expected: "2 digit day",
+                },
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Err(expected), DateTime::from_rfc3339(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_datetime_max_representable_date() {
+        let fields = DateTime::MAX.fields();
+        assert_eq!((9999, 12, 31), (fields.year, fields.month, fields.day));
+        assert_eq!(
+            (23, 59, 59, 999),
+            (
+                fields.hour,
+                fields.minute,
+                fields.second,
+                fields.millisecond
+            )
+        );
+        assert_eq!("9999-12-31 23:59:59.999", DateTime::MAX.format());
+        assert_eq!(
+            Ok(DateTime::MAX),
+            DateTime::from_rfc3339("9999-12-31T23:59:59.999Z")
+        );
+        assert_eq!(
+            DateTime::new(9999, 12, 31, 23, 59, 59).to_milliseconds() + 999,
+            DateTime::MAX.to_milliseconds()
+        );
+        assert_eq!("0000-01-01 00:00:00.000", DateTime::MIN.format());
+    }
+
+    #[test]
+    fn test_datetime_filetime() {
+        let filetime_epoch = DateTime::from_filetime(0);
+        assert_eq!(DateTime::new(1601, 1, 1, 0, 0, 0), filetime_epoch);
+        assert_eq!(0, filetime_epoch.to_filetime());
+        // 2023-05-28T14:36:46.0761234Z
+        let datetime = DateTime::from_filetime(133_297_582_060_761_234);
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            datetime
+        );
+        assert_eq!(133_297_582_060_760_000, datetime.to_filetime());
+        assert_eq!(0, DateTime::new(1600, 12, 31, 0, 0, 0).to_filetime());
+    }
+
+    #[test]
+    fn test_datetime_dotnet_ticks() {
+        // new DateTime(2023, 5, 28, 14, 36, 46, 76, DateTimeKind.Utc).Ticks
+        let ticks = 638_208_814_060_760_000;
+        let datetime = DateTime::from_dotnet_ticks(ticks + 1_234);
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            datetime
+        );
+        assert_eq!(ticks, datetime.to_dotnet_ticks());
+        // DateTime.UnixEpoch.Ticks
+        assert_eq!(621_355_968_000_000_000, DateTime::epoch().to_dotnet_ticks());
+        assert_eq!(
+            DateTime::new(1, 1, 1, 0, 0, 0),
+            DateTime::from_dotnet_ticks(0)
+        );
+        assert_eq!(DateTime::MIN, DateTime::from_dotnet_ticks(i64::MIN));
+    }
+
+    #[test]
+    fn test_datetime_coarse_age() {
+        let now = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let cases = [
+            (DateTime::new(2020, 5, 1, 0, 0, 0), "3y"),
+            (DateTime::new(2023, 3, 15, 0, 0, 0), "2mo"),
+            (DateTime::new(2023, 5, 25, 12, 0, 0), "3d"),
+            (DateTime::new(2023, 5, 28, 9, 30, 0), "5h"),
+            (DateTime::new(2023, 5, 28, 14, 32, 0), "4m"),
+            (DateTime::new(2023, 5, 28, 14, 36, 16), "30s"),
+            (now.clone(), "0s"),
+            (DateTime::new(2023, 5, 30, 14, 36, 46), "2d"),
+        ];
+        for (datetime, expected) in cases {
+            assert_eq!(expected, datetime.coarse_age(&now), "{}", datetime.format());
+        }
+    }
+
+    #[test]
+    fn test_datetime_cmp_with_tolerance() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let tolerance = Time::from_milliseconds(500);
+        let close = DateTime::from_milliseconds(datetime.to_milliseconds() + 500);
+        assert_eq!(
+            Ordering::Equal,
+            datetime.cmp_with_tolerance(&close, tolerance.clone())
+        );
+        assert_eq!(
+            Ordering::Equal,
+            close.cmp_with_tolerance(&datetime, tolerance.clone())
+        );
+        let later = DateTime::from_milliseconds(datetime.to_milliseconds() + 501);
+        assert_eq!(
+            Ordering::Less,
+            datetime.cmp_with_tolerance(&later, tolerance.clone())
+        );
+        assert_eq!(
+            Ordering::Greater,
+            later.cmp_with_tolerance(&datetime, tolerance)
+        );
+        assert_eq!(
+            Ordering::Less,
+            datetime.cmp_with_tolerance(&close, Time::from_milliseconds(0))
+        );
+    }
+
+    #[test]
+    fn test_datetime_now_truncated() {
+        let before = DateTime::now_truncated_to(Time::from_minutes(1));
+        let now = DateTime::now_seconds();
+        assert_eq!(0, before.get_seconds_of_minute());
+        assert_eq!(0, before.get_milliseconds_of_second());
+        assert_eq!(0, now.get_milliseconds_of_second());
+        assert!(before <= now);
+        let clock = std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_685_284_606_076);
+        assert_eq!(
+            "2023-05-28 14:36:00.000",
+            DateTime::now_truncated_to_at(Time::from_minutes(1), clock).format()
+        );
+        assert_eq!(
+            "2023-05-28 14:00:00.000",
+            DateTime::now_truncated_to_at(Time::from_hours(1), clock).format()
+        );
+        assert_eq!(
+            "2023-05-28 14:36:46.000",
+            DateTime::now_seconds_at(clock).format()
+        );
+    }
+
+    #[test]
+    fn test_datetime_format_cow() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let label = datetime.format_cow("daily report");
+        assert!(matches!(label, Cow::Borrowed("daily report")));
+        let label = datetime.format_cow("report %Y-%m-%d");
+        assert!(matches!(label, Cow::Owned(_)));
+        assert_eq!("report 2023-05-28", label);
+    }
+
+    #[test]
+    fn test_datetime_parse_trailing_z() {
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let format = "%Y-%m-%d %H:%M:%S.%f";
+        assert_eq!(
+            Ok(expected.clone()),
+            DateTime::parse("2023-05-28 14:36:46.076Z", format)
+        );
+        assert_eq!(
+            Ok(expected.clone()),
+            DateTime::parse("2023-05-28 14:36:46.076z", format)
+        );
+        assert_eq!(
+            Ok(expected.clone()),
+            DateTime::parse("2023-05-28 14:36:46.076", format)
+        );
+        assert_eq!(
+            Ok(expected),
+            DateTime::parse_flexible("2023-05-28 14:36:46.076Z")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingInput { position: 24 }),
+            DateTime::parse("2023-05-28 14:36:46.076ZZ", format)
+        );
+    }
+
+    #[test]
+    fn test_datetime_ics() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!("20230528T143646Z", datetime.to_ics());
+        let parsed = DateTime::from_ics(&datetime.to_ics()).unwrap();
+        assert_eq!(DateTime::new(2023, 5, 28, 14, 36, 46), parsed);
+        assert_eq!(
+            parsed.to_ics(),
+            DateTime::from_ics(&parsed.to_ics()).unwrap().to_ics()
+        );
+        assert_eq!(
+            Err(ParseError::UnexpectedEnd { position: 15 }),
+            DateTime::from_ics("20230528T143646")
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 4,
+                expected: "2 digit month"
+            }),
+            DateTime::from_ics("2023-05-28T14:36:46Z")
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "day",
+                value: 30
+            }),
+            DateTime::from_ics("20230230T143646Z")
+        );
+    }
+
+    #[test]
+    fn test_datetime_day_of_week_reference_dates() {
+        let references = [
+            ((0, 1, 1), Weekday::Sunday),
+            ((0, 12, 31), Weekday::Sunday),
+            ((1, 1, 1), Weekday::Monday),
+            ((1582, 10, 15), Weekday::Friday),
+            ((1776, 7, 4), Weekday::Thursday),
+            ((1900, 1, 1), Weekday::Monday),
+            ((1969, 7, 20), Weekday::Sunday),
+            ((1970, 1, 1), Weekday::Thursday),
+            ((2000, 1, 1), Weekday::Saturday),
+            ((2000, 2, 29), Weekday::Tuesday),
+            ((2001, 9, 11), Weekday::Tuesday),
+            ((9999, 12, 31), Weekday::Friday),
+        ];
+        for ((year, month, day), expected) in references {
+            let datetime = DateTime::new(year, month, day, 23, 59, 59);
+            assert_eq!(expected, datetime.weekday(), "{}-{}-{}", year, month, day);
+        }
+        assert_eq!(0, DateTime::MIN.to_days());
+        assert_eq!(1, DateTime::new(0, 1, 2, 0, 0, 0).to_days());
+    }
+
+    #[test]
+    fn test_datetime_excel_serial() {
+        assert_eq!(
+            Ok(DateTime::new(1900, 1, 1, 0, 0, 0)),
+            DateTime::from_excel_serial(1.0)
+        );
+        assert_eq!(
+            Ok(DateTime::new(1900, 2, 28, 0, 0, 0)),
+            DateTime::from_excel_serial(59.0)
+        );
+        assert_eq!(
+            Ok(DateTime::new(1900, 2, 28, 0, 0, 0)),
+            DateTime::from_excel_serial(60.0)
+        );
+        assert_eq!(
+            Ok(DateTime::new(1900, 3, 1, 0, 0, 0)),
+            DateTime::from_excel_serial(61.0)
+        );
+        assert_eq!(1.0, DateTime::new(1900, 1, 1, 0, 0, 0).to_excel_serial());
+        assert_eq!(59.0, DateTime::new(1900, 2, 28, 0, 0, 0).to_excel_serial());
+        assert_eq!(61.0, DateTime::new(1900, 3, 1, 0, 0, 0).to_excel_serial());
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(
+            Ok(datetime.clone()),
+            DateTime::from_excel_serial(45_074.608_865_740_74)
+        );
+        assert!((datetime.to_excel_serial() - 45_074.608_865_740_74).abs() < 1e-9);
+        assert_eq!(
+            Ok(DateTime::new(2023, 5, 28, 12, 0, 0)),
+            DateTime::from_excel_serial(45_074.5)
+        );
+    }
+
+    #[test]
+    fn test_datetime_months_between() {
+        let start = DateTime::new(2024, 1, 15, 0, 0, 0);
+        let end = DateTime::new(2024, 3, 15, 0, 0, 0);
+        let months: Vec<DateTime> = DateTime::months_between(&start, &end).collect();
+        assert_eq!(
+            vec![
+                DateTime::new(2024, 2, 1, 0, 0, 0),
+                DateTime::new(2024, 3, 1, 0, 0, 0)
+            ],
+            months
+        );
+        let start = DateTime::new(2023, 11, 1, 0, 0, 0);
+        let end = DateTime::new(2024, 3, 1, 0, 0, 0);
+        let months: Vec<String> = DateTime::months_between(&start, &end)
+            .map(|month| time_format(month.to_milliseconds(), "%Y-%m-%d"))
+            .collect();
+        assert_eq!(
+            vec!["2023-11-01", "2023-12-01", "2024-01-01", "2024-02-01"],
+            months
+        );
+        assert_eq!(0, DateTime::months_between(&end, &start).count());
+    }
+
+    #[test]
+    fn test_datetime_epoch_day() {
+        assert_eq!(0, DateTime::epoch().epoch_day());
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(19_505, datetime.epoch_day());
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 0, 0, 0),
+            DateTime::from_epoch_day(19_505)
+        );
+        assert_eq!(-1, DateTime::new(1969, 12, 31, 23, 59, 59).epoch_day());
+        assert_eq!(
+            DateTime::new(1969, 12, 31, 0, 0, 0),
+            DateTime::from_epoch_day(-1)
+        );
+        assert_eq!(DateTime::MIN, DateTime::from_epoch_day(i64::MIN));
+    }
+
+    #[test]
+    fn test_datetime_parse_leap_second() {
+        let format = "%Y-%m-%d %H:%M:%S";
+        let datetime =
+            DateTime::parse_with_mode("2016-12-31 23:59:60", format, ParseMode::Lenient).unwrap();
+        assert_eq!("2016-12-31 23:59:59.999", datetime.format());
+        assert!(datetime < DateTime::new(2017, 1, 1, 0, 0, 0));
+        assert_eq!(
+            Ok(datetime),
+            DateTime::from_rfc3339("2016-12-31T23:59:60.5Z")
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "second",
+                value: 61
+            }),
+            DateTime::parse_with_mode("2016-12-31 23:59:61", format, ParseMode::Lenient)
+        );
+    }
+
+    #[test]
+    fn test_datetime_parse_modes() {
+        let format = "%Y-%m-%d %H:%M:%S";
+        let leap_second = Err(ParseError::OutOfRange {
+            field: "second",
+            value: 60,
+        });
+        assert_eq!(leap_second, DateTime::parse("2016-12-31 23:59:60", format));
+        assert_eq!(
+            leap_second,
+            DateTime::parse_with_mode("2016-12-31 23:59:60", format, ParseMode::Strict)
+        );
+        assert_eq!(
+            Ok(DateTime::from_milliseconds(
+                DateTime::new(2016, 12, 31, 23, 59, 59).to_milliseconds() + 999
+            )),
+            DateTime::parse_with_mode("2016-12-31 23:59:60", format, ParseMode::Lenient)
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "day",
+                value: 30
+            }),
+            DateTime::parse("2023-02-30 00:00:00", format)
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 2, 28, 0, 0, 0)),
+            DateTime::parse_with_mode("2023-02-30 00:00:00", format, ParseMode::Lenient)
+        );
+        assert!(
+            DateTime::parse_with_mode("2023-02-32 00:00:00", format, ParseMode::Lenient).is_err()
+        );
+    }
+
+    #[test]
+    fn test_datetime_now_unix_millis_and_seconds() {
+        let clock =
+            std::time::UNIX_EPOCH + std::time::Duration::from_nanos(1_685_284_606_076_999_999);
+        assert_eq!(1_685_284_606_076, DateTime::now_unix_millis_at(clock));
+        assert_eq!(1_685_284_606, DateTime::now_unix_seconds_at(clock));
+        assert_eq!(0, DateTime::now_unix_millis_at(std::time::UNIX_EPOCH));
+        assert_eq!(
+            0,
+            DateTime::now_unix_seconds_at(
+                std::time::UNIX_EPOCH + std::time::Duration::from_millis(999)
+            )
+        );
+        let before = DateTime::now().to_unix_epoch_milliseconds();
+        let millis = DateTime::now_unix_millis();
+        let seconds = DateTime::now_unix_seconds();
+        let alias = now_milliseconds();
+        let after = DateTime::now().to_unix_epoch_milliseconds();
+        assert!(before <= millis && millis <= after);
+        assert!(before / 1000 <= seconds && seconds <= after / 1000);
+        assert!(millis <= alias && alias <= after);
+    }
+
+    #[test]
+    fn test_datetime_try_new_checked_weekday() {
+        let datetime =
+            DateTime::try_new_checked_weekday(2023, 5, 28, 14, 36, 46, Weekday::Sunday).unwrap();
+        assert_eq!(DateTime::new(2023, 5, 28, 14, 36, 46), datetime);
+        assert_eq!(
+            Err(TimeError::WeekdayMismatch {
+                expected: Weekday::Monday,
+                actual: Weekday::Sunday
+            }),
+            DateTime::try_new_checked_weekday(2023, 5, 28, 14, 36, 46, Weekday::Monday)
+        );
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "day",
+                value: 29
+            }),
+            DateTime::try_new_checked_weekday(2023, 2, 29, 0, 0, 0, Weekday::Wednesday)
+        );
+    }
+
+    #[test]
+    fn test_datetime_format_parts() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(
+            vec![
+                FormatPart::Field {
+                    kind: FieldKind::Year,
+                    text: String::from("2023")
+                },
+                FormatPart::Literal(String::from("-")),
+                FormatPart::Field {
+                    kind: FieldKind::Month,
+                    text: String::from("05")
+                },
+            ],
+            datetime.format_parts("%Y-%m")
+        );
+        let format = "'at' %H:%M%.f on %A, %B %d";
+        let parts = datetime.format_parts(format);
+        let joined: String = parts.iter().map(FormatPart::text).collect();
+        assert_eq!(datetime.format_cow(format), joined);
+        assert_eq!(FormatPart::Literal(String::from("at ")), parts[0]);
+        assert_eq!(FormatPart::Literal(String::from(" on ")), parts[4]);
+        assert_eq!(
+            FormatPart::Field {
+                kind: FieldKind::WeekdayName,
+                text: String::from("Sunday")
+            },
+            parts[5]
+        );
+        let datetime = DateTime::new(0, 1, 1, 14, 0, 0);
+        for format in ["%D %H", "%H %D", " %D %H:%M "] {
+            let parts = datetime.format_parts(format);
+            let joined: String = parts.iter().map(FormatPart::text).collect();
+            assert_eq!(datetime.format_cow(format), joined);
+        }
+        assert_eq!(
+            vec![FormatPart::Field {
+                kind: FieldKind::Hour,
+                text: String::from("14")
+            }],
+            datetime.format_parts("%D %H")
+        );
+    }
+
+    #[test]
+    fn test_datetime_unix_millis_round_trip_sweep() {
+        let first = -EPOCH_START;
+        let last = DateTime::MAX.to_unix_epoch_milliseconds();
+        let stride = 86_399_999_997;
+        let edges = [first, first + 1, -1, 0, 1, last - 1, last];
+        let sweep = (first..=last).step_by(stride);
+        for unix in sweep.chain(edges) {
+            let datetime = DateTime::from_unix_epoch_milliseconds(unix);
+            assert_eq!(unix, datetime.to_unix_epoch_milliseconds());
+            assert_eq!(
+                datetime,
+                DateTime::from_milliseconds(datetime.to_milliseconds())
+            );
+        }
+        assert_eq!(
+            0,
+            DateTime::from_unix_epoch_milliseconds(first).to_milliseconds()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "before AD 0")]
+    fn test_datetime_from_unix_epoch_milliseconds_before_ad_zero_panics() {
+        DateTime::from_unix_epoch_milliseconds(-EPOCH_START - 1);
+    }
+
+    #[test]
+    fn test_datetime_unix_epoch_seconds_f64() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let seconds = datetime.to_unix_epoch_seconds_f64();
+        assert_eq!("1685284606.076", format!("{:.3}", seconds));
+        assert_eq!(
+            Ok(datetime.clone()),
+            DateTime::from_unix_epoch_seconds_f64(seconds)
+        );
+        assert_eq!(
+            Ok(datetime),
+            DateTime::from_unix_epoch_seconds_f64(1_685_284_606.076_4)
+        );
+        let before_epoch = DateTime::new(1969, 12, 31, 23, 59, 59);
+        assert_eq!(-1.0, before_epoch.to_unix_epoch_seconds_f64());
+        assert_eq!(
+            Ok(DateTime::MAX),
+            DateTime::from_unix_epoch_seconds_f64(DateTime::MAX.to_unix_epoch_seconds_f64())
+        );
+        assert_eq!(
+            Ok(DateTime::MIN),
+            DateTime::from_unix_epoch_seconds_f64(DateTime::MIN.to_unix_epoch_seconds_f64())
+        );
+        assert_eq!(
+            Ok(DateTime::MAX),
+            DateTime::from_unix_epoch_seconds_f64(1e300)
+        );
+        assert_eq!(
+            Ok(DateTime::MIN),
+            DateTime::from_unix_epoch_seconds_f64(-1e20)
+        );
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                Err(TimeError::NotFinite { field: "seconds" }),
+                DateTime::from_unix_epoch_seconds_f64(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_datetime_from_rfc3339_truncates_fraction() {
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_123);
+        assert_eq!(
+            Ok(expected.clone()),
+            DateTime::from_rfc3339("2023-05-28T14:36:46.123999999Z")
+        );
+        assert_eq!(
+            Ok(expected.clone()),
+            DateTime::from_rfc3339("2023-05-28T14:36:46.1239Z")
+        );
+        assert_eq!(
+            Ok(DateTime::from_unix_epoch_milliseconds(1_685_284_606_999)),
+            DateTime::from_rfc3339("2023-05-28T14:36:46.999999999Z")
+        );
+        assert_eq!(
+            Ok(expected),
+            DateTime::parse("2023-05-28 14:36:46.123999999", "%Y-%m-%d %H:%M:%S.%f")
+        );
+    }
+
+    #[test]
+    fn test_datetime_weeks_between() {
+        let start = DateTime::new(2024, 2, 1, 0, 0, 0);
+        let end = DateTime::new(2024, 3, 1, 0, 0, 0);
+        assert_eq!(4, DateTime::weeks_between(&start, &end));
+        assert_eq!(-4, DateTime::weeks_between(&end, &start));
+        let almost = DateTime::new(2024, 2, 7, 23, 59, 59);
+        assert_eq!(0, DateTime::weeks_between(&start, &almost));
+        assert_eq!(1, DateTime::weeks_between(&start, &almost.add_days(1)));
+    }
+
+    #[test]
+    fn test_datetime_iso_week_starts_between() {
+        let start = DateTime::new(2024, 4, 1, 0, 0, 0);
+        let end = DateTime::new(2024, 4, 22, 0, 0, 0);
+        let mondays: Vec<DateTime> = DateTime::iso_week_starts_between(&start, &end).collect();
+        assert_eq!(
+            vec![
+                DateTime::new(2024, 4, 1, 0, 0, 0),
+                DateTime::new(2024, 4, 8, 0, 0, 0),
+                DateTime::new(2024, 4, 15, 0, 0, 0),
+            ],
+            mondays
+        );
+        assert!(mondays
+            .iter()
+            .all(|monday| monday.weekday() == Weekday::Monday));
+        let after_midnight = DateTime::new(2024, 4, 1, 0, 0, 1);
+        assert_eq!(
+            Some(DateTime::new(2024, 4, 8, 0, 0, 0)),
+            DateTime::iso_week_starts_between(&after_midnight, &end).next()
+        );
+        assert_eq!(0, DateTime::iso_week_starts_between(&end, &start).count());
+    }
+
+    #[test]
+    fn test_datetime_duration_since_orderings() {
+        let earlier = DateTime::new(2023, 5, 28, 14, 0, 0);
+        let later = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let expected = Time::new(0, 0, 36, 46);
+        assert_eq!(
+            Some(expected.clone()),
+            later.checked_duration_since(&earlier)
+        );
+        assert_eq!(expected, later.saturating_duration_since(&earlier));
+        assert_eq!(None, earlier.checked_duration_since(&later));
+        assert_eq!(Time::ZERO, earlier.saturating_duration_since(&later));
+        assert_eq!(Some(Time::ZERO), later.checked_duration_since(&later));
+    }
+
+    #[test]
+    fn test_datetime_format_uses_compiled_default_layout() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!("2023-05-28 14:36:46.076", datetime.format());
+        assert_eq!("0000-01-01 00:00:00.000", DateTime::MIN.format());
+        assert_eq!("9999-12-31 23:59:59.999", DateTime::MAX.format());
+        for offset in 0..10_000 {
+            let datetime = DateTime::from_milliseconds(datetime.to_milliseconds() + offset * 7_919);
+            assert_eq!(
+                time_format(datetime.to_milliseconds(), DEFAULT_LAYOUT),
+                datetime.format()
+            );
+        }
+    }
+
+    #[test]
+    fn test_datetime_fields_weekday_matches_weekday() {
+        for datetime in [
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            DateTime::new(2024, 1, 1, 0, 0, 0),
+            DateTime::new(2024, 2, 29, 23, 59, 59),
+            DateTime::MIN,
+            DateTime::MAX,
+        ] {
+            let fields = datetime.fields();
+            assert_eq!(datetime.weekday(), fields.weekday());
+            assert_eq!(datetime.get_day_of_week(), fields.day_of_week);
+            assert_eq!(datetime.get_day_of_year(), fields.day_of_year);
+        }
+    }
+
+    #[test]
+    fn test_datetime_is_midnight() {
+        let midnight = DateTime::new(2023, 5, 28, 0, 0, 0);
+        assert!(midnight.is_midnight());
+        assert!(DateTime::MIN.is_midnight());
+        let after = DateTime::from_milliseconds(midnight.to_milliseconds() + 1);
+        assert_eq!("2023-05-28 00:00:00.001", after.format());
+        assert!(!after.is_midnight());
+        assert!(!DateTime::MAX.is_midnight());
+    }
+
+    /// Serializes and deserializes `datetime` under each serde mode, asserting equality within the mode's precision.
+    fn assert_serde_roundtrip(datetime: &DateTime) {
+        #[derive(Debug, Deserialize, Serialize)]
+        struct Modes {
+            standard: DateTime,
+            #[serde(with = "crate::datetime_or_none")]
+            optional: Option<DateTime>,
+            #[serde(with = "crate::unix_seconds")]
+            seconds: DateTime,
+            ad_millis: AdMillis,
+            unix_millis: UnixMillis,
+        }
+        let modes = Modes {
+            standard: datetime.clone(),
+            optional: Some(datetime.clone()),
+            seconds: datetime.clone(),
+            ad_millis: datetime.to_ad_millis(),
+            unix_millis: datetime.to_unix_millis(),
+        };
+        let json = serde_json::to_string(&modes).unwrap();
+        let restored: Modes = serde_json::from_str(&json).unwrap();
+        assert_eq!(datetime, &restored.standard, "standard form of {}", json);
+        assert_eq!(
+            Some(datetime),
+            restored.optional.as_ref(),
+            "optional form of {}",
+            json
+        );
+        assert_eq!(
+            datetime.truncate_to(Time::from_seconds(1)),
+            restored.seconds,
+            "Unix seconds form of {}",
+            json
+        );
+        assert_eq!(Ok(datetime.clone()), DateTime::try_from(restored.ad_millis));
+        assert_eq!(
+            Ok(datetime.clone()),
+            DateTime::try_from(restored.unix_millis)
+        );
+        let unix_number = datetime.to_unix_epoch_milliseconds().to_string();
+        assert_eq!(
+            datetime,
+            &serde_json::from_str::<DateTime>(&unix_number).unwrap()
+        );
+        let unix_string = format!("\"{}\"", unix_number);
+        assert_eq!(
+            datetime,
+            &serde_json::from_str::<DateTime>(&unix_string).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_datetime_serde_roundtrip_matrix() {
+        for datetime in [
+            DateTime::MIN,
+            DateTime::from_milliseconds(1),
+            DateTime::new(1969, 12, 31, 23, 59, 59),
+            DateTime::from_unix_epoch_milliseconds(-1),
+            DateTime::epoch(),
+            DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+            DateTime::new(2024, 2, 29, 12, 0, 0),
+            DateTime::MAX,
+        ] {
+            assert_serde_roundtrip(&datetime);
+        }
+    }
+
+    #[test]
+    fn test_datetime_try_new() {
+        assert_eq!(
+            Ok(DateTime::new(2024, 2, 29, 23, 59, 59)),
+            DateTime::try_new(2024, 2, 29, 23, 59, 59)
+        );
+        let out_of_range = |field, value| Err(TimeError::OutOfRange { field, value });
+        assert_eq!(
+            out_of_range("hour", 25),
+            DateTime::try_new(2023, 5, 28, 25, 0, 0)
+        );
+        assert_eq!(
+            out_of_range("day", 29),
+            DateTime::try_new(2023, 2, 29, 0, 0, 0)
+        );
+        assert_eq!(
+            out_of_range("month", 0),
+            DateTime::try_new(2023, 0, 1, 0, 0, 0)
+        );
+        assert_eq!(
+            out_of_range("second", 60),
+            DateTime::try_new(2023, 5, 28, 0, 0, 60)
+        );
+        assert_eq!(
+            out_of_range("year", 10000),
+            DateTime::try_new(10000, 1, 1, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "hour value 25 is out of range")]
+    fn test_datetime_new_panics_on_invalid_hour_in_debug() {
+        DateTime::new(2023, 5, 28, 25, 0, 0);
+    }
+
+    #[test]
+    fn test_datetime_range_inclusive() {
+        let start = DateTime::new(2024, 1, 1, 0, 0, 0);
+        let aligned = DateTime::new(2024, 1, 1, 1, 0, 0);
+        let times: Vec<DateTime> =
+            DateTime::range_inclusive(&start, &aligned, Time::from_minutes(20)).collect();
+        assert_eq!(4, times.len());
+        assert_eq!(Some(&aligned), times.last());
+        let unaligned = DateTime::new(2024, 1, 1, 0, 50, 0);
+        let times: Vec<DateTime> =
+            DateTime::range_inclusive(&start, &unaligned, Time::from_minutes(20)).collect();
+        assert_eq!(
+            vec![
+                start.clone(),
+                DateTime::new(2024, 1, 1, 0, 20, 0),
+                DateTime::new(2024, 1, 1, 0, 40, 0),
+            ],
+            times
+        );
+        assert_eq!(
+            vec![start.clone()],
+            DateTime::range_inclusive(&start, &start, Time::from_days(1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            1,
+            DateTime::range_inclusive(&start, &aligned, Time::ZERO).count()
+        );
+        assert_eq!(
+            0,
+            DateTime::range_inclusive(&aligned, &start, Time::from_minutes(1)).count()
+        );
+    }
+
+    #[test]
+    fn test_datetime_format_elapsed_since() {
+        let start = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let later = DateTime::from_milliseconds(start.to_milliseconds() + 1_234);
+        assert_eq!("+1.234s", later.format_elapsed_since(&start));
+        assert_eq!("-1.234s", start.format_elapsed_since(&later));
+        assert_eq!("+0.000s", start.format_elapsed_since(&start));
+        let much_later = DateTime::new(2023, 5, 28, 16, 36, 46);
+        assert_eq!("+7200.000s", much_later.format_elapsed_since(&start));
+    }
+
+    #[test]
+    fn test_datetime_from_unix_epoch_nanoseconds() {
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(
+            expected,
+            DateTime::from_unix_epoch_nanoseconds(1_685_284_606_076_999_999)
+        );
+        assert_eq!(
+            expected,
+            DateTime::from_unix_epoch_nanoseconds(1_685_284_606_076_000_000)
+        );
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(-1),
+            DateTime::from_unix_epoch_nanoseconds(-1)
+        );
+        assert_eq!(
+            DateTime::epoch(),
+            DateTime::from_unix_epoch_nanoseconds(999_999)
+        );
+    }
+
+    #[test]
+    fn test_datetime_parse_round_trips_format() {
+        let now = DateTime::now();
+        assert_eq!(
+            Ok(now.clone()),
+            DateTime::parse(&now.format(), DEFAULT_LAYOUT)
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 5, 28, 14, 36, 46)),
+            DateTime::parse("2023-05-28 14:36:46", "%Y-%m-%d %H:%M:%S")
+        );
+        let format = "%Y-%m-%d 100%%";
+        let datetime = DateTime::new(2023, 5, 28, 0, 0, 0);
+        assert_eq!(
+            Ok(datetime.clone()),
+            DateTime::parse(&datetime.format_cow(format), format)
+        );
+        let error = DateTime::parse("2023-05-28 14:36:46", DEFAULT_LAYOUT).unwrap_err();
+        assert_eq!(ParseError::UnexpectedEnd { position: 19 }, error);
+        let error = DateTime::parse("2023-05-28T14:36:46.076", DEFAULT_LAYOUT).unwrap_err();
+        assert_eq!("expected literal text at byte 10", error.to_string());
+    }
+
+    #[test]
+    fn test_datetime_truncate_to_crosses_midnight() {
+        let bucket = Time::from_minutes(90);
+        assert_eq!(
+            DateTime::new(2023, 5, 27, 22, 30, 0),
+            DateTime::new(2023, 5, 27, 23, 59, 59).truncate_to(bucket.clone())
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 0, 0, 0),
+            DateTime::new(2023, 5, 28, 0, 10, 0).truncate_to(bucket.clone())
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 1, 30, 0),
+            DateTime::new(2023, 5, 28, 1, 30, 0).truncate_to(bucket)
+        );
+        let bucket = Time::from_hours(7);
+        let before_midnight = DateTime::new(2023, 5, 27, 23, 0, 0).truncate_to(bucket.clone());
+        let after_midnight = DateTime::new(2023, 5, 28, 0, 30, 0).truncate_to(bucket);
+        assert_eq!(DateTime::new(2023, 5, 27, 22, 0, 0), after_midnight);
+        assert_eq!(before_midnight, after_midnight);
+    }
+
+    #[test]
+    fn test_datetime_from_str() {
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(
+            Ok(expected.clone()),
+            "2023-05-28T14:36:46.076Z".parse::<DateTime>()
+        );
+        assert_eq!(
+            Ok(expected.clone()),
+            "2023-05-28T16:36:46.076+02:00".parse()
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 5, 28, 14, 36, 46)),
+            "2023-05-28T14:36:46Z".parse()
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 4,
+                expected: "'-'"
+            }),
+            "2023/05/28T14:36:46Z".parse::<DateTime>()
+        );
+        assert!("".parse::<DateTime>().is_err());
+    }
+
+    #[test]
+    fn test_datetime_to_rfc3339() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!("2023-05-28T14:36:46.076Z", datetime.to_rfc3339());
+        assert_eq!(datetime.to_rfc3339(), datetime.to_iso8601());
+        assert_eq!(Ok(datetime.clone()), datetime.to_rfc3339().parse());
+        let padded = DateTime::new(5, 1, 2, 3, 4, 5);
+        assert_eq!("0005-01-02T03:04:05.000Z", padded.to_rfc3339());
+        assert_eq!(
+            Ok(padded.clone()),
+            DateTime::from_rfc3339(&padded.to_iso8601())
+        );
+    }
+
+    #[test]
+    fn test_datetime_business_day_of_month() {
+        // April 2023 starts on a Saturday.
+        assert_eq!(
+            0,
+            DateTime::new(2023, 4, 1, 0, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            0,
+            DateTime::new(2023, 4, 2, 12, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            1,
+            DateTime::new(2023, 4, 3, 0, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            5,
+            DateTime::new(2023, 4, 7, 0, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            5,
+            DateTime::new(2023, 4, 9, 0, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            20,
+            DateTime::new(2023, 4, 28, 0, 0, 0).business_day_of_month()
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 4, 3, 0, 0, 0)),
+            DateTime::nth_business_day_of_month(2023, 4, 1)
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 4, 10, 0, 0, 0)),
+            DateTime::nth_business_day_of_month(2023, 4, 6)
+        );
+        assert_eq!(
+            Ok(DateTime::new(2023, 4, 28, 0, 0, 0)),
+            DateTime::nth_business_day_of_month(2023, 4, 20)
+        );
+        let out_of_range = |value| {
+            Err(TimeError::OutOfRange {
+                field: "business_day",
+                value,
+            })
+        };
+        assert_eq!(
+            out_of_range(21),
+            DateTime::nth_business_day_of_month(2023, 4, 21)
+        );
+        assert_eq!(
+            out_of_range(0),
+            DateTime::nth_business_day_of_month(2023, 4, 0)
+        );
+        assert!(DateTime::nth_business_day_of_month(2023, 13, 1).is_err());
+    }
+
+    #[test]
+    fn test_datetime_add_and_subtract_time() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(
+            DateTime::new(2023, 6, 4, 14, 36, 46),
+            datetime.add(&Time::from_weeks(1))
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 11, 36, 46),
+            datetime.subtract(&Time::from_hours(3))
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 29, 0, 0, 0),
+            datetime.clone() + Time::new(0, 9, 23, 14)
+        );
+        assert_eq!(
+            DateTime::new(2023, 5, 28, 0, 0, 0),
+            datetime.clone() - Time::new(0, 14, 36, 46)
+        );
+        assert_eq!(DateTime::MIN, datetime.clone() - Time::from_weeks(200_000));
+        assert_eq!(DateTime::MAX, datetime + Time::from_milliseconds(u64::MAX));
+        let latest = DateTime::MIN + Time::from_milliseconds(u64::MAX);
+        assert_eq!(DateTime::MAX, latest);
+        assert_eq!(9999, latest.get_year());
+        assert_eq!(
+            DateTime::MAX,
+            DateTime::MAX.add(&Time::from_milliseconds(1))
+        );
+    }
+
+    #[test]
+    fn test_datetime_duration_since_is_absolute() {
+        let earlier = DateTime::new(2023, 5, 28, 14, 0, 0);
+        let later = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(Time::new(0, 0, 36, 46), later.duration_since(&earlier));
+        assert_eq!(Time::new(0, 0, 36, 46), earlier.duration_since(&later));
+        assert_eq!(2_206_000, later.signed_duration_since(&earlier));
+        assert_eq!(-2_206_000, earlier.signed_duration_since(&later));
+        assert_eq!(Time::ZERO, later.duration_since(&later));
+        assert_eq!(
+            DateTime::MAX.to_milliseconds() as u64,
+            DateTime::MIN
+                .duration_since(&DateTime::MAX)
+                .to_milliseconds()
+        );
+    }
+
+    #[test]
+    fn test_datetime_deserialize_compact_struct_form() {
+        use serde_test::{assert_tokens, Configure, Token};
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_tokens(
+            &datetime.clone().compact(),
+            &[
+                Token::Struct {
+                    name: "DateTime",
+                    len: 1,
+                },
+                Token::Str("milliseconds"),
+                Token::I64(datetime.to_milliseconds()),
+                Token::StructEnd,
+            ],
+        );
+        serde_test::assert_de_tokens(
+            &datetime.clone().compact(),
+            &[
+                Token::Seq { len: Some(1) },
+                Token::I64(datetime.to_milliseconds()),
+                Token::SeqEnd,
+            ],
+        );
+        serde_test::assert_de_tokens_error::<serde_test::Compact<DateTime>>(
+            &[Token::I64(1_685_284_606_076)],
+            "invalid type: integer `1685284606076`, expected struct DateTimeStruct",
+        );
+        serde_test::assert_de_tokens(
+            &DateTime::from_unix_epoch_milliseconds(1_685_284_606_076).readable(),
+            &[Token::I64(1_685_284_606_076)],
+        );
+    }
+
+    #[test]
+    fn test_datetime_parse_quoted_literals_round_trip() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        assert_eq!(
+            Ok(DateTime::new(2023, 1, 1, 0, 0, 0)),
+            DateTime::parse("at 2023", "'at' %Y")
+        );
+        for format in [
+            "'at' %Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d'T'%H:%M:%S",
+            "'%Y' %Y-%m-%d %H:%M:%S",
+            "'o''clock' %Y-%m-%d %H:%M:%S%% ''",
+            "%Y-%m-%d %H:%M:%S%,f",
+        ] {
+            let formatted = datetime.format_cow(format);
+            assert_eq!(Ok(datetime.clone()), DateTime::parse(&formatted, format));
+        }
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 0,
+                expected: "a field supported by DateTime::parse",
+            }),
+            DateTime::parse("Sunday", "%A")
+        );
+    }
+
+    #[test]
+    fn test_datetime_constructors_respect_max() {
+        let max = DateTime::MAX.to_milliseconds();
+        assert_eq!(Ok(DateTime::MAX), DateTime::try_from(max));
+        assert_eq!(
+            Err(TimeError::OutOfRange {
+                field: "milliseconds",
+                value: (max + 1) as i128
+            }),
+            DateTime::try_from(max + 1)
+        );
+        assert!(DateTime::try_from(i64::MAX).is_err());
+        assert!(DateTime::try_from(i64::MAX as u64).is_err());
+        let last = DateTime::MAX.to_unix_epoch_milliseconds();
+        assert_eq!(
+            Ok(DateTime::MAX),
+            DateTime::try_from_unix_epoch_milliseconds(last)
+        );
+        assert!(DateTime::try_from_unix_epoch_milliseconds(last + 1).is_err());
+        assert_eq!(DateTime::MAX, DateTime::from_filetime(u64::MAX));
+        assert_eq!(DateTime::MAX, DateTime::from_dotnet_ticks(i64::MAX));
+        assert_eq!(Ok(DateTime::MAX), DateTime::from_excel_serial(1e300));
+        assert_eq!(Ok(DateTime::MIN), DateTime::from_excel_serial(-1e300));
+        assert_eq!(DateTime::MAX, DateTime::from_epoch_day(i64::MAX));
+        assert_eq!(DateTime::MAX, DateTime::MIN.add_days(i64::MAX));
+        assert_eq!(None, DateTime::MAX.checked_add_days(1));
+        assert_eq!(
+            DateTime::MAX,
+            DateTime::MIN.add_precise_truncating(&PreciseTime::from_nanoseconds(u128::MAX))
+        );
+        assert_eq!(
+            DateTime::MAX,
+            DateTime::MAX.to_local(TimeZoneOffset::from_hours(14))
+        );
+        assert_eq!(9999, DateTime::MAX.get_year());
+    }
+
+    #[test]
+    #[should_panic(expected = "after DateTime::MAX")]
+    fn test_datetime_from_unix_epoch_milliseconds_after_max_panics() {
+        DateTime::from_unix_epoch_milliseconds(DateTime::MAX.to_unix_epoch_milliseconds() + 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "DateTime::new: year value 10000 is out of range")]
+    fn test_datetime_new_rejects_year_after_9999_in_debug() {
+        DateTime::new(10000, 1, 1, 0, 0, 0);
+    }
+
+    #[test]
+    fn test_datetime_months_between_ends_at_max() {
+        let start = DateTime::new(9999, 11, 15, 0, 0, 0);
+        let months: Vec<String> = DateTime::months_between(&start, &DateTime::MAX)
+            .map(|month| month.format())
+            .collect();
+        assert_eq!(vec!["9999-12-01 00:00:00.000"], months);
+        let start = DateTime::new(9999, 12, 15, 0, 0, 0);
+        assert_eq!(0, DateTime::months_between(&start, &DateTime::MAX).count());
+    }
+
+    #[test]
+    fn test_datetime_excel_serial_not_finite() {
+        for serial in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(
+                Err(TimeError::NotFinite { field: "serial" }),
+                DateTime::from_excel_serial(serial)
+            );
+        }
+    }
+
     // /// Debug test for verifying that the current time is formatted correctly.
     // #[test]
     // fn test_datetime_formatting_from_now() {
@@ -0,0 +1,175 @@
+//! Serde helpers for an optional `DateTime` that is stored as `0` when there is no value.
+//!
+//! Deserializing maps `0` (or `"0"`, or `null`) to `None`, and any other value is deserialized as a `DateTime`.
+//! Serializing writes `0` for `None`, and the standard `DateTime` form otherwise.
+//!
+//! Formats that are not human readable, such as bincode or postcard, cannot tell `0` apart from a `DateTime`,
+//! so they store the value as a regular `Option<DateTime>` instead.
+//!
+//! Example:
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use stoicdreams_timestamp::prelude::*;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Record {
+//!     #[serde(with = "stoicdreams_timestamp::datetime_or_none")]
+//!     deleted: Option<DateTime>,
+//! }
+//!
+//! let record: Record = serde_json::from_str(r#"{"deleted":0}"#).unwrap();
+//! assert_eq!(record.deleted, None);
+//! ```
+use crate::datetime::DateTimeVisitor;
+use crate::prelude::*;
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+pub fn serialize<S>(value: &Option<DateTime>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if !serializer.is_human_readable() {
+        return value.serialize(serializer);
+    }
+    match value {
+        Some(datetime) => datetime.serialize(serializer),
+        None => serializer.serialize_i64(0),
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if !deserializer.is_human_readable() {
+        return Option::<DateTime>::deserialize(deserializer);
+    }
+    deserializer.deserialize_any(OptionalDateTimeVisitor)
+}
+
+struct OptionalDateTimeVisitor;
+
+impl<'de> Visitor<'de> for OptionalDateTimeVisitor {
+    type Value = Option<DateTime>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("0 for no value, or a DateTime")
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Option<DateTime>, E> {
+        match value {
+            0 => Ok(None),
+            _ => DateTimeVisitor.visit_i64(value).map(Some),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Option<DateTime>, E> {
+        match value {
+            0 => Ok(None),
+            _ => DateTimeVisitor.visit_u64(value).map(Some),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Option<DateTime>, E> {
+        match value.trim() {
+            "0" => Ok(None),
+            _ => DateTimeVisitor.visit_str(value).map(Some),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Option<DateTime>, A::Error> {
+        DateTimeVisitor.visit_map(map).map(Some)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<DateTime>, E> {
+        Ok(None)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<DateTime>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Option<DateTime>, D::Error> {
+        deserializer.deserialize_any(OptionalDateTimeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_tokens, Configure, Token};
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Record {
+        name: String,
+        #[serde(with = "crate::datetime_or_none")]
+        deleted: Option<DateTime>,
+    }
+
+    #[test]
+    fn test_datetime_or_none_zero_sentinel() {
+        let record: Record = serde_json::from_str(r#"{"name":"a","deleted":0}"#).unwrap();
+        assert_eq!(None, record.deleted);
+        let record: Record = serde_json::from_str(r#"{"name":"a","deleted":"0"}"#).unwrap();
+        assert_eq!(None, record.deleted);
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(r#"{"name":"a","deleted":0}"#, json);
+    }
+
+    #[test]
+    fn test_datetime_or_none_with_value() {
+        let record: Record =
+            serde_json::from_str(r#"{"name":"a","deleted":1685284606076}"#).unwrap();
+        let expected = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        assert_eq!(Some(expected.clone()), record.deleted);
+        let json = serde_json::to_string(&record).unwrap();
+        let record: Record = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(expected), record.deleted);
+    }
+
+    #[test]
+    fn test_datetime_or_none_compact_round_trip() {
+        let tokens = |deleted: &[Token]| {
+            let mut tokens = vec![
+                Token::Struct {
+                    name: "Record",
+                    len: 2,
+                },
+                Token::Str("name"),
+                Token::Str("a"),
+                Token::Str("deleted"),
+            ];
+            tokens.extend_from_slice(deleted);
+            tokens.push(Token::StructEnd);
+            tokens
+        };
+        let record = Record {
+            name: String::from("a"),
+            deleted: None,
+        };
+        assert_tokens(&record.compact(), &tokens(&[Token::None]));
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let record = Record {
+            name: String::from("a"),
+            deleted: Some(datetime.clone()),
+        };
+        assert_tokens(
+            &record.compact(),
+            &tokens(&[
+                Token::Some,
+                Token::Struct {
+                    name: "DateTime",
+                    len: 1,
+                },
+                Token::Str("milliseconds"),
+                Token::I64(datetime.to_milliseconds()),
+                Token::StructEnd,
+            ]),
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::weekday::Weekday;
+use std::fmt;
+
+/// Errors returned when parsing a date or time from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input ended at the byte `position` while more input was expected.
+    UnexpectedEnd { position: usize },
+    /// The input at the byte `position` did not match what was `expected`.
+    Unexpected {
+        position: usize,
+        expected: &'static str,
+    },
+    /// A parsed `field` has a `value` outside of its valid range.
+    OutOfRange { field: &'static str, value: i64 },
+    /// A parsed `field` is too large to be stored.
+    Overflow { field: &'static str },
+    /// Unparsed input remains starting at the byte `position`.
+    TrailingInput { position: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd { position } => {
+                write!(f, "unexpected end of input at byte {}", position)
+            }
+            ParseError::Unexpected { position, expected } => {
+                write!(f, "expected {} at byte {}", expected, position)
+            }
+            ParseError::OutOfRange { field, value } => {
+                write!(f, "{} value {} is out of range", field, value)
+            }
+            ParseError::Overflow { field } => {
+                write!(f, "{} value is too large", field)
+            }
+            ParseError::TrailingInput { position } => {
+                write!(f, "unexpected trailing input at byte {}", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Errors returned when date or time values are invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeError {
+    /// A `field` has a `value` outside of its valid range.
+    /// The value is an `i128` so that any `i64` or `u64` input can be reported as given.
+    OutOfRange { field: &'static str, value: i128 },
+    /// A floating point `field` is NaN or infinite.
+    NotFinite { field: &'static str },
+    /// A date falls on the `actual` weekday instead of the `expected` one.
+    WeekdayMismatch { expected: Weekday, actual: Weekday },
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeError::OutOfRange { field, value } => {
+                write!(f, "{} value {} is out of range", field, value)
+            }
+            TimeError::NotFinite { field } => {
+                write!(f, "{} value is not a finite number", field)
+            }
+            TimeError::WeekdayMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "expected {} but the date falls on {}",
+                    expected.name(),
+                    actual.name()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimeError {}
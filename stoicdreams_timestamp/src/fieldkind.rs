@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of value in a `FormatPart::Field`, returned by `DateTime::format_parts()`.
+///
+/// Padded, unpadded, and shortened forms of a value share a kind, e.g. `%Y`, `%-Y`, and `%y` are all `Year`,
+/// and both `%B` and `%b` are `MonthName`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum FieldKind {
+    /// `%Y`, `%-Y`, or `%y`
+    Year,
+    /// `%C`
+    Century,
+    /// `%m`
+    Month,
+    /// `%B` or `%b`
+    MonthName,
+    /// `%d`
+    Day,
+    /// `%D`
+    Days,
+    /// `%H`
+    Hour,
+    /// `%M`
+    Minute,
+    /// `%S`
+    Second,
+    /// `%f`, `%.f`, or `%,f`
+    Fraction,
+    /// `%u`
+    Weekday,
+    /// `%A` or `%a`
+    WeekdayName,
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A segment of a formatted `DateTime`, returned by `DateTime::format_parts()`.
+///
+/// Renderers can use the parts to style each field separately (e.g. wrapping fields in spans),
+/// while joining the text of every part gives the same result as `DateTime::format_cow()`.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+/// let parts = datetime.format_parts("%Y-%m");
+/// assert_eq!(parts[0], FormatPart::Field { kind: FieldKind::Year, text: String::from("2023") });
+/// assert_eq!(parts[1], FormatPart::Literal(String::from("-")));
+/// assert_eq!(parts[2].text(), "05");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum FormatPart {
+    /// Text copied from the format as is.
+    Literal(String),
+    /// The formatted value of a `%` field.
+    Field { kind: FieldKind, text: String },
+}
+
+impl FormatPart {
+    /// Returns the text of this part.
+    pub fn text(&self) -> &str {
+        match self {
+            FormatPart::Literal(text) => text,
+            FormatPart::Field { text, .. } => text,
+        }
+    }
+}
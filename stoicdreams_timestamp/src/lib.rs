@@ -1,7 +1,28 @@
+pub mod admillis;
+pub mod calendarsystem;
+pub mod calendarunit;
+pub mod datefields;
 pub mod datetime;
+pub mod datetime_or_none;
+pub mod error;
+pub mod fieldkind;
+pub mod formatpart;
+pub mod month;
+pub mod parsemode;
+mod parser;
+pub mod precise_time_string;
+pub mod precisedatetime;
 pub mod precisetime;
 pub mod prelude;
+pub mod scopetimer;
+pub mod signedtime;
 pub mod stopwatch;
 pub mod time;
 pub mod time_helpers;
 pub mod timestamp;
+pub mod timezoneoffset;
+pub mod unix_seconds;
+pub mod unixmillis;
+pub mod wallstopwatch;
+pub mod weekday;
+pub mod zoneddatetime;
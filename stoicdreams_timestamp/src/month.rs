@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const MONTH_SHORT_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const MONTHS: [Month; 12] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
+/// A month of the year, numbered the same as `DateTime::get_month()` where 1 is January and 12 is December.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let month = Month::from_number(5).unwrap();
+/// assert_eq!(month, Month::May);
+/// assert_eq!(month.name(), "May");
+/// assert_eq!(Month::from_name("dec"), Some(Month::December));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Month {
+    January = 1,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl Month {
+    /// Returns the month for a month number, where 1 is January and 12 is December.
+    pub fn from_number(month: u8) -> Option<Self> {
+        match month {
+            1..=12 => Some(MONTHS[month as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// Returns the month matching a full (`"January"`) or short (`"Jan"`) English name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..12)
+            .find(|&index| {
+                MONTH_NAMES[index].eq_ignore_ascii_case(name)
+                    || MONTH_SHORT_NAMES[index].eq_ignore_ascii_case(name)
+            })
+            .map(|index| MONTHS[index])
+    }
+
+    /// Returns the month number, where 1 is January and 12 is December.
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the full English name (e.g. `"January"`).
+    pub fn name(&self) -> &'static str {
+        MONTH_NAMES[*self as usize - 1]
+    }
+
+    /// Returns the three letter English abbreviation (e.g. `"Jan"`).
+    pub fn short_name(&self) -> &'static str {
+        MONTH_SHORT_NAMES[*self as usize - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_names_round_trip() {
+        for number in 1..=12 {
+            let month = Month::from_number(number).unwrap();
+            assert_eq!(number, month.number());
+            assert_eq!(Some(month), Month::from_name(month.name()));
+            assert_eq!(Some(month), Month::from_name(month.short_name()));
+        }
+        assert_eq!(None, Month::from_number(0));
+        assert_eq!(None, Month::from_number(13));
+        assert_eq!(None, Month::from_name("Smarch"));
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// How `DateTime::parse_with_mode()` handles values that are outside of their valid range.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let format = "%Y-%m-%d %H:%M:%S";
+/// assert!(DateTime::parse_with_mode("2016-12-31 23:59:60", format, ParseMode::Strict).is_err());
+/// let datetime = DateTime::parse_with_mode("2016-12-31 23:59:60", format, ParseMode::Lenient).unwrap();
+/// assert_eq!(datetime.format(), "2016-12-31 23:59:59.999");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ParseMode {
+    /// Reject any value outside of its valid range, including a leap second (`:60`).
+    #[default]
+    Strict,
+    /// Clamp near-miss values into range instead of rejecting them:
+    /// a leap second (`:60`) becomes `:59.999`, and a day past the end of its month (up to 31) becomes the month's last day.
+    Lenient,
+}
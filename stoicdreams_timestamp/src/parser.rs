@@ -0,0 +1,227 @@
+use crate::prelude::*;
+
+/// A cursor over the bytes of an input string, used by the date and time parsers.
+/// Values are read by indexing into the input, so parsing does not allocate.
+pub(crate) struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    /// Returns the current byte offset into the input.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.position).copied()
+    }
+
+    /// Consumes the next byte when it matches `byte`, returning whether it was consumed.
+    pub(crate) fn optional_byte(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Consumes the next byte, which must match `byte`.
+    pub(crate) fn expect_byte(
+        &mut self,
+        byte: u8,
+        expected: &'static str,
+    ) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(next) if next == byte => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) => Err(self.unexpected(expected)),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    /// Consumes exactly `count` ASCII digits and returns their value.
+    pub(crate) fn digits(
+        &mut self,
+        count: usize,
+        expected: &'static str,
+    ) -> Result<u32, ParseError> {
+        let (value, digits) = self.digits_between(count, count, expected)?;
+        debug_assert_eq!(count, digits);
+        Ok(value)
+    }
+
+    /// Consumes between `min` and `max` ASCII digits, returning their value and the number of digits read.
+    pub(crate) fn digits_between(
+        &mut self,
+        min: usize,
+        max: usize,
+        expected: &'static str,
+    ) -> Result<(u32, usize), ParseError> {
+        let start = self.position;
+        let mut value: u32 = 0;
+        while self.position - start < max {
+            match self.peek() {
+                Some(byte) if byte.is_ascii_digit() => {
+                    value = value * 10 + (byte - b'0') as u32;
+                    self.position += 1;
+                }
+                _ => break,
+            }
+        }
+        let count = self.position - start;
+        if count < min {
+            return match self.peek() {
+                Some(_) => Err(self.unexpected(expected)),
+                None => Err(self.unexpected_end()),
+            };
+        }
+        Ok((value, count))
+    }
+
+    /// Consumes one or more ASCII digits with no upper limit on their count, returning their value.
+    /// A value too large for a `u128` returns `ParseError::Overflow` for `field`.
+    pub(crate) fn unbounded_digits(
+        &mut self,
+        field: &'static str,
+        expected: &'static str,
+    ) -> Result<u128, ParseError> {
+        let start = self.position;
+        let mut value: u128 = 0;
+        while let Some(byte) = self.peek().filter(u8::is_ascii_digit) {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add((byte - b'0') as u128))
+                .ok_or(ParseError::Overflow { field })?;
+            self.position += 1;
+        }
+        if self.position == start {
+            return match self.peek() {
+                Some(_) => Err(self.unexpected(expected)),
+                None => Err(self.unexpected_end()),
+            };
+        }
+        Ok(value)
+    }
+
+    /// Consumes a fractional second of 1 to 9 digits (the digits after the decimal point) and returns it in nanoseconds.
+    /// Digits beyond nanosecond precision are not accepted.
+    pub(crate) fn fraction_nanoseconds(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<u32, ParseError> {
+        let (value, digits) = self.digits_between(1, 9, expected)?;
+        Ok(value * 10u32.pow(9 - digits as u32))
+    }
+
+    /// Consumes a run of ASCII letters, which must not be empty.
+    pub(crate) fn alphabetic(&mut self, expected: &'static str) -> Result<&'a str, ParseError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(byte) if byte.is_ascii_alphabetic()) {
+            self.position += 1;
+        }
+        if self.position == start {
+            return match self.peek() {
+                Some(_) => Err(self.unexpected(expected)),
+                None => Err(self.unexpected_end()),
+            };
+        }
+        Ok(&self.input[start..self.position])
+    }
+
+    /// Consumes the literal text, which must match exactly.
+    pub(crate) fn expect_literal(
+        &mut self,
+        literal: &str,
+        expected: &'static str,
+    ) -> Result<(), ParseError> {
+        for byte in literal.bytes() {
+            self.expect_byte(byte, expected)?;
+        }
+        Ok(())
+    }
+
+    /// Confirms that all of the input has been consumed.
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        if self.is_at_end() {
+            return Ok(());
+        }
+        Err(ParseError::TrailingInput {
+            position: self.position,
+        })
+    }
+
+    pub(crate) fn unexpected(&self, expected: &'static str) -> ParseError {
+        ParseError::Unexpected {
+            position: self.position,
+            expected,
+        }
+    }
+
+    pub(crate) fn unexpected_end(&self) -> ParseError {
+        ParseError::UnexpectedEnd {
+            position: self.position,
+        }
+    }
+}
+
+/// Parses a duration in the layout of `Time::format()`, `[D ]HH:MM:SS[.fraction]`, and returns it in nanoseconds.
+///
+/// Hours must be below 24 when a days prefix is present, otherwise they may be any number of digits.
+/// The leading days or hours are not limited in length, and a total too large for a `u128` returns `ParseError::Overflow`.
+/// The optional fraction must have exactly `fraction_digits` digits.
+pub(crate) fn duration_nanoseconds(
+    input: &str,
+    fraction_digits: usize,
+    fraction_expected: &'static str,
+) -> Result<u128, ParseError> {
+    fn check(field: &'static str, value: u32, max: u32) -> Result<(), ParseError> {
+        if value > max {
+            return Err(ParseError::OutOfRange {
+                field,
+                value: value as i64,
+            });
+        }
+        Ok(())
+    }
+    let mut parser = Parser::new(input);
+    let leading = parser.unbounded_digits("duration", "days or hours")?;
+    let (days, hours) = if parser.optional_byte(b' ') {
+        let hours = parser.digits(2, "2 digit hour")?;
+        check("hour", hours, 23)?;
+        (leading, hours as u128)
+    } else {
+        (0, leading)
+    };
+    parser.expect_byte(b':', "':'")?;
+    let minutes = parser.digits(2, "2 digit minute")?;
+    check("minute", minutes, 59)?;
+    parser.expect_byte(b':', "':'")?;
+    let seconds = parser.digits(2, "2 digit second")?;
+    check("second", seconds, 59)?;
+    let mut fraction = 0;
+    if parser.optional_byte(b'.') {
+        fraction = parser.digits(fraction_digits, fraction_expected)?;
+    }
+    parser.finish()?;
+    let fraction = fraction as u128 * 10u128.pow(9 - fraction_digits as u32);
+    days.checked_mul(24)
+        .and_then(|hours_total| hours_total.checked_add(hours))
+        .and_then(|hours| hours.checked_mul(60))
+        .and_then(|minutes_total| minutes_total.checked_add(minutes as u128))
+        .and_then(|minutes| minutes.checked_mul(60))
+        .and_then(|seconds_total| seconds_total.checked_add(seconds as u128))
+        .and_then(|seconds| seconds.checked_mul(1_000_000_000))
+        .and_then(|nanoseconds| nanoseconds.checked_add(fraction))
+        .ok_or(ParseError::Overflow { field: "duration" })
+}
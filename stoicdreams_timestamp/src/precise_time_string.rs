@@ -0,0 +1,83 @@
+//! Serde helpers for storing a `PreciseTime` as its formatted string, e.g. `"1 02:03:04.005006007"`.
+//!
+//! The default `PreciseTime` form holds a `u128` nanosecond count, which many JSON parsers (including Javascript's)
+//! cannot represent exactly. The formatted string keeps full nanosecond precision in any JSON parser.
+//!
+//! Example:
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use stoicdreams_timestamp::prelude::*;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Measurement {
+//!     #[serde(with = "stoicdreams_timestamp::precise_time_string")]
+//!     duration: PreciseTime,
+//! }
+//!
+//! let measurement = Measurement { duration: PreciseTime::new(0, 0, 0, 1, 500, 0, 7) };
+//! let json = serde_json::to_string(&measurement).unwrap();
+//! assert_eq!(json, r#"{"duration":"00:00:01.500000007"}"#);
+//! ```
+use crate::prelude::*;
+use serde::de::{self, Deserialize};
+use serde::{Deserializer, Serializer};
+
+pub fn serialize<S>(value: &PreciseTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&value.format())
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<PreciseTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    PreciseTime::from_format(&value).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Measurement {
+        #[serde(with = "crate::precise_time_string")]
+        duration: PreciseTime,
+    }
+
+    #[test]
+    fn test_precise_time_string_round_trip() {
+        let duration = PreciseTime::new(1, 2, 3, 4, 5, 6, 7);
+        let json = serde_json::to_string(&Measurement {
+            duration: duration.clone(),
+        })
+        .unwrap();
+        assert_eq!(r#"{"duration":"1 02:03:04.005006007"}"#, json);
+        let measurement: Measurement = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            duration.to_nanoseconds(),
+            measurement.duration.to_nanoseconds()
+        );
+    }
+
+    #[test]
+    fn test_precise_time_string_rejects_invalid() {
+        assert!(serde_json::from_str::<Measurement>(r#"{"duration":"soon"}"#).is_err());
+        assert!(serde_json::from_str::<Measurement>(r#"{"duration":1000}"#).is_err());
+    }
+
+    #[test]
+    fn test_precise_time_string_large_days_round_trip() {
+        let measurement: Measurement =
+            serde_json::from_str(r#"{"duration":"1041666666 16:00:00.000000000"}"#).unwrap();
+        assert_eq!(
+            (1_041_666_666 * 24 + 16) * 60 * 60 * 1_000_000_000,
+            measurement.duration.to_nanoseconds()
+        );
+        let json = serde_json::to_string(&measurement).unwrap();
+        assert_eq!(r#"{"duration":"1041666666 16:00:00.000000000"}"#, json);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A date and time struct that represents a time in nanoseconds since the start of AD 0.
+///
+/// Use it when sub-millisecond precision matters, e.g. for ordering events recorded in quick succession.
+/// `DateTime` only stores milliseconds, so `DateTime::to_precise()` is lossless while `PreciseDateTime::to_datetime()`
+/// truncates any nanoseconds below the millisecond.
+///
+/// Expected usage is to use one of the following methods to create a new PreciseDateTime struct:
+/// - `DateTime::to_precise()`
+/// - `PreciseDateTime::from_nanoseconds(nanoseconds)`
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+/// let precise = PreciseDateTime::from_nanoseconds(datetime.to_precise().to_nanoseconds() + 123_456);
+/// assert_eq!(precise.format(), "2023-05-28 14:36:46.076123456");
+/// assert_eq!(precise.to_datetime(), datetime);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct PreciseDateTime {
+    /// Nanoseconds since the start of AD 0.
+    nanoseconds: PreciseTimeUnit,
+}
+
+impl PreciseDateTime {
+    /// Create a new `PreciseDateTime` from nanoseconds since the start of AD 0.
+    pub fn from_nanoseconds(nanoseconds: PreciseTimeUnit) -> Self {
+        Self { nanoseconds }
+    }
+
+    /// Returns the `DateTime` at the start of the same millisecond, truncating any remaining nanoseconds.
+    pub fn to_datetime(&self) -> DateTime {
+        let milliseconds = self.nanoseconds / 1_000_000;
+        DateTime::from_milliseconds(
+            DateTimeUnit::try_from(milliseconds).unwrap_or(DateTimeUnit::MAX),
+        )
+    }
+
+    pub fn format(&self) -> String {
+        precise_time_format(self.nanoseconds, "%Y-%m-%d %H:%M:%S.%f")
+    }
+
+    /// Returns the total number of nanoseconds since 0 AD.
+    pub fn to_nanoseconds(&self) -> PreciseTimeUnit {
+        self.nanoseconds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precise_datetime_conversions() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let precise = datetime.to_precise();
+        assert_eq!(
+            datetime.to_milliseconds() as PreciseTimeUnit * 1_000_000,
+            precise.to_nanoseconds()
+        );
+        assert_eq!(datetime, precise.to_datetime());
+        assert_eq!("2023-05-28 14:36:46.076000000", precise.format());
+        let precise = PreciseDateTime::from_nanoseconds(precise.to_nanoseconds() + 999_999);
+        assert_eq!(datetime, precise.to_datetime());
+        assert!(precise > datetime.to_precise());
+    }
+}
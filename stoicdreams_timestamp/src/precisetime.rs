@@ -1,12 +1,17 @@
+use crate::parser::duration_nanoseconds;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub type PreciseTimeUnit = u128;
 
 /// A precise time struct that can be used to represent a time in nanoseconds.
 /// Expected usage is to use one of the following methods to create a new PreciseTime struct:
 /// - `PreciseTime::new(days, hours, minutes, seconds, milliseconds, microseconds, nanoseconds)`
+/// - `PreciseTime::from_parts(days, hours, minutes, seconds, milliseconds, microseconds, nanoseconds)` - for values too large for `new()`
 /// - `PreciseTime::from_nanoseconds(nanoseconds)`
+/// - `PreciseTime::from_duration(duration)` - lossless for every `std::time::Duration`
+/// - `PreciseTime::from_format(value)` - to parse the output of `PreciseTime::format()`
 ///
 /// Example:
 /// ```
@@ -167,14 +172,72 @@ impl PreciseTime {
         Self { nanoseconds }
     }
 
+    /// Create a new `PreciseTime` from wide values, which may exceed their usual range (e.g. 90 minutes) and are added together.
+    /// Every combination of `u64` inputs fits within the `u128` nanoseconds, so this never overflows.
+    pub fn from_parts(
+        days: u64,
+        hours: u64,
+        minutes: u64,
+        seconds: u64,
+        milliseconds: u64,
+        microseconds: u64,
+        nanoseconds: u64,
+    ) -> Self {
+        let nanoseconds = nanoseconds as PreciseTimeUnit
+            + (microseconds as PreciseTimeUnit * 1000)
+            + (milliseconds as PreciseTimeUnit * 1000 * 1000)
+            + (seconds as PreciseTimeUnit * 1000 * 1000 * 1000)
+            + (minutes as PreciseTimeUnit * 60 * 1000 * 1000 * 1000)
+            + (hours as PreciseTimeUnit * 60 * 60 * 1000 * 1000 * 1000)
+            + (days as PreciseTimeUnit * 24 * 60 * 60 * 1000 * 1000 * 1000);
+        Self { nanoseconds }
+    }
+
     pub fn from_nanoseconds(nanoseconds: PreciseTimeUnit) -> Self {
         Self { nanoseconds }
     }
 
+    /// Create a new `PreciseTime` from a `std::time::Duration`.
+    /// Every `Duration` (up to `Duration::MAX`) fits within the `u128` nanoseconds, so this is lossless and never overflows.
+    pub fn from_duration(duration: Duration) -> Self {
+        Self::from_nanoseconds(duration.as_nanos())
+    }
+
+    /// Parses a `PreciseTime` from the layout produced by `PreciseTime::format()`, `"[D ]HH:MM:SS.nnnnnnnnn"`,
+    /// where the leading number of days is optional, and so is the 9 digit fraction of a second.
+    pub fn from_format(value: &str) -> Result<PreciseTime, ParseError> {
+        let nanoseconds = duration_nanoseconds(value, 9, "9 digit nanoseconds")?;
+        Ok(Self { nanoseconds })
+    }
+
     pub fn format(&self) -> String {
         precise_time_format(self.nanoseconds, "%D %H:%M:%S.%f")
     }
 
+    /// Formats the time using the largest unit (ns, µs, ms, or s) that has a nonzero value,
+    /// with up to three fractional digits (truncated, trailing zeros removed).
+    /// e.g. `"456 ns"`, `"1.5 µs"`, `"1.234 ms"`, `"2 s"`
+    pub fn format_auto(&self) -> String {
+        let (unit_size, unit_name): (PreciseTimeUnit, &str) = match self.nanoseconds {
+            0..=999 => (1, "ns"),
+            1_000..=999_999 => (1_000, "µs"),
+            1_000_000..=999_999_999 => (1_000_000, "ms"),
+            _ => (1_000_000_000, "s"),
+        };
+        let whole = self.nanoseconds / unit_size;
+        let fraction = (self.nanoseconds % unit_size) * 1000 / unit_size;
+        if fraction == 0 {
+            return format!("{} {}", whole, unit_name);
+        }
+        let fraction = format!("{:03}", fraction);
+        format!("{}.{} {}", whole, fraction.trim_end_matches('0'), unit_name)
+    }
+
+    /// Returns true when this time is zero nanoseconds long.
+    pub fn is_zero(&self) -> bool {
+        self.nanoseconds == 0
+    }
+
     /// Returns the total number of nanoseconds.
     pub fn to_nanoseconds(&self) -> PreciseTimeUnit {
         self.nanoseconds
@@ -234,4 +297,84 @@ mod tests {
         assert_eq!(49, time.get_minutes_of_hour());
         assert_eq!(27, time.get_seconds_of_minute());
     }
+
+    #[test]
+    fn test_precise_time_format_auto() {
+        assert_eq!("0 ns", PreciseTime::from_nanoseconds(0).format_auto());
+        assert_eq!("500 ns", PreciseTime::from_nanoseconds(500).format_auto());
+        assert_eq!("1.5 µs", PreciseTime::from_nanoseconds(1_500).format_auto());
+        assert_eq!(
+            "1.234 ms",
+            PreciseTime::from_nanoseconds(1_234_567).format_auto()
+        );
+        assert_eq!(
+            "2 s",
+            PreciseTime::from_nanoseconds(2_000_000_000).format_auto()
+        );
+        assert_eq!(
+            "90.05 s",
+            PreciseTime::from_nanoseconds(90_050_000_000).format_auto()
+        );
+    }
+
+    #[test]
+    fn test_precise_time_from_format() {
+        let time = PreciseTime::new(1, 2, 3, 4, 5, 6, 7);
+        assert_eq!("1 02:03:04.005006007", time.format());
+        let parsed = PreciseTime::from_format(&time.format()).unwrap();
+        assert_eq!(time.to_nanoseconds(), parsed.to_nanoseconds());
+        let time = PreciseTime::new(0, 2, 3, 4, 5, 6, 7);
+        assert_eq!("02:03:04.005006007", time.format());
+        let parsed = PreciseTime::from_format(&time.format()).unwrap();
+        assert_eq!(time.to_nanoseconds(), parsed.to_nanoseconds());
+        let parsed = PreciseTime::from_format("00:00:01").unwrap();
+        assert_eq!(1_000_000_000, parsed.to_nanoseconds());
+        assert_eq!(
+            ParseError::UnexpectedEnd { position: 12 },
+            PreciseTime::from_format("00:00:01.005").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_precise_time_from_parts() {
+        let time = PreciseTime::from_parts(100_000, 0, 0, 0, 0, 0, 0);
+        assert_eq!(100_000, time.to_days());
+        assert_eq!(
+            100_000 * 24 * 60 * 60 * 1_000_000_000,
+            time.to_nanoseconds()
+        );
+        let time = PreciseTime::from_parts(0, 0, 90, 0, 1_500, 0, 0);
+        assert_eq!(
+            PreciseTime::new(0, 1, 30, 1, 500, 0, 0).to_nanoseconds(),
+            time.to_nanoseconds()
+        );
+        let time = PreciseTime::from_parts(
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+            u64::MAX,
+        );
+        assert!(time.to_days() > u64::MAX as PreciseTimeUnit);
+    }
+
+    #[test]
+    fn test_precise_time_is_zero() {
+        assert!(PreciseTime::from_nanoseconds(0).is_zero());
+        assert!(!PreciseTime::from_nanoseconds(1).is_zero());
+    }
+
+    #[test]
+    fn test_precise_time_from_duration() {
+        let time = PreciseTime::from_duration(Duration::new(1, 500_000_007));
+        assert_eq!(1_500_000_007, time.to_nanoseconds());
+        let time = PreciseTime::from_duration(Duration::new(u64::MAX, 999_999_999));
+        assert_eq!(
+            u64::MAX as u128 * 1_000_000_000 + 999_999_999,
+            time.to_nanoseconds()
+        );
+        assert_eq!(u64::MAX as u128, time.to_seconds());
+    }
 }
@@ -1,7 +1,33 @@
+pub use crate::admillis;
+pub use crate::admillis::*;
+pub use crate::calendarsystem;
+pub use crate::calendarsystem::*;
+pub use crate::calendarunit;
+pub use crate::calendarunit::*;
+pub use crate::datefields;
+pub use crate::datefields::*;
 pub use crate::datetime;
 pub use crate::datetime::*;
+pub use crate::datetime_or_none;
+pub use crate::error;
+pub use crate::error::*;
+pub use crate::fieldkind;
+pub use crate::fieldkind::*;
+pub use crate::formatpart;
+pub use crate::formatpart::*;
+pub use crate::month;
+pub use crate::month::*;
+pub use crate::parsemode;
+pub use crate::parsemode::*;
+pub use crate::precise_time_string;
+pub use crate::precisedatetime;
+pub use crate::precisedatetime::*;
 pub use crate::precisetime;
 pub use crate::precisetime::*;
+pub use crate::scopetimer;
+pub use crate::scopetimer::*;
+pub use crate::signedtime;
+pub use crate::signedtime::*;
 pub use crate::stopwatch;
 pub use crate::stopwatch::*;
 pub use crate::time;
@@ -10,3 +36,14 @@ pub use crate::time_helpers;
 pub use crate::time_helpers::*;
 pub use crate::timestamp;
 pub use crate::timestamp::*;
+pub use crate::timezoneoffset;
+pub use crate::timezoneoffset::*;
+pub use crate::unix_seconds;
+pub use crate::unixmillis;
+pub use crate::unixmillis::*;
+pub use crate::wallstopwatch;
+pub use crate::wallstopwatch::*;
+pub use crate::weekday;
+pub use crate::weekday::*;
+pub use crate::zoneddatetime;
+pub use crate::zoneddatetime::*;
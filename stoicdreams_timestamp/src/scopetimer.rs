@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+/// A guard that measures the time it is alive and reports `label: <elapsed>` when it is dropped.
+///
+/// Expected usage is to use one of the following methods to create a new ScopeTimer struct:
+/// - `StopWatch::scope(label)` - prints to standard output.
+/// - `ScopeTimer::with_sink(label, sink)` - sends the message to a custom closure.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// {
+///     let _guard = StopWatch::scope("db query");
+///     // prints "db query: 1.234 ms" when the scope ends
+/// }
+/// ```
+pub struct ScopeTimer {
+    label: String,
+    stopwatch: StopWatch,
+    sink: Option<Box<dyn FnOnce(String)>>,
+}
+
+impl ScopeTimer {
+    /// Create a ScopeTimer that prints its message to standard output.
+    pub fn new(label: &str) -> Self {
+        Self::with_sink(label, |message| println!("{}", message))
+    }
+
+    /// Create a ScopeTimer that sends its message to the provided `sink` when dropped.
+    pub fn with_sink(label: &str, sink: impl FnOnce(String) + 'static) -> Self {
+        Self {
+            label: label.to_string(),
+            stopwatch: StopWatch::start(),
+            sink: Some(Box::new(sink)),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn stopwatch(&self) -> &StopWatch {
+        &self.stopwatch
+    }
+}
+
+impl Drop for ScopeTimer {
+    fn drop(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            sink(self.stopwatch.report(&self.label));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_scope_timer_reports_on_drop() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        {
+            let captured = messages.clone();
+            let guard = ScopeTimer::with_sink("db query", move |message| {
+                captured.borrow_mut().push(message)
+            });
+            assert_eq!("db query", guard.label());
+            assert!(messages.borrow().is_empty());
+        }
+        let messages = messages.borrow();
+        assert_eq!(1, messages.len());
+        assert!(messages[0].starts_with("db query: "));
+        assert!(messages[0].ends_with('s'));
+    }
+}
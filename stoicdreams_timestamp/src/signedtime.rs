@@ -0,0 +1,131 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+
+/// A duration that may be negative, such as "ahead of schedule by 5 minutes".
+///
+/// `Time` is unsigned, so `SignedTime` pairs a millisecond count with a sign.
+/// Displaying prefixes `-` to the `Time::format()` output for negative values,
+/// and adding or subtracting keeps the sign of the result.
+///
+/// Expected usage is to use one of the following methods to create a new SignedTime struct:
+/// - `SignedTime::from_milliseconds(milliseconds)`
+/// - `SignedTime::positive(time)`
+/// - `SignedTime::negative(time)`
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let ahead = SignedTime::negative(Time::from_minutes(5));
+/// assert_eq!(ahead.to_string(), "-00:05:00.000");
+/// assert_eq!(ahead.abs(), Time::from_minutes(5));
+///
+/// let total = ahead + SignedTime::positive(Time::from_minutes(2));
+/// assert_eq!(total.to_string(), "-00:03:00.000");
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize,
+)]
+pub struct SignedTime {
+    milliseconds: i64,
+}
+
+impl SignedTime {
+    /// Create a new `SignedTime` from a signed number of milliseconds.
+    pub fn from_milliseconds(milliseconds: i64) -> Self {
+        Self { milliseconds }
+    }
+
+    /// Create a new positive `SignedTime`, saturating at `i64::MAX` milliseconds.
+    pub fn positive(time: Time) -> Self {
+        Self::from_milliseconds(i64::try_from(time.to_milliseconds()).unwrap_or(i64::MAX))
+    }
+
+    /// Create a new negative `SignedTime`, saturating at `-i64::MAX` milliseconds.
+    pub fn negative(time: Time) -> Self {
+        -Self::positive(time)
+    }
+
+    /// Returns the signed number of milliseconds.
+    pub fn to_milliseconds(&self) -> i64 {
+        self.milliseconds
+    }
+
+    /// Returns true when this duration is below zero.
+    pub fn is_negative(&self) -> bool {
+        self.milliseconds < 0
+    }
+
+    /// Returns the magnitude of this duration, dropping the sign.
+    pub fn abs(&self) -> Time {
+        Time::from_milliseconds(self.milliseconds.unsigned_abs())
+    }
+}
+
+impl From<Time> for SignedTime {
+    fn from(time: Time) -> Self {
+        Self::positive(time)
+    }
+}
+
+impl fmt::Display for SignedTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.abs().format())
+    }
+}
+
+impl Add for SignedTime {
+    type Output = SignedTime;
+
+    fn add(self, other: SignedTime) -> SignedTime {
+        Self::from_milliseconds(self.milliseconds.saturating_add(other.milliseconds))
+    }
+}
+
+impl Sub for SignedTime {
+    type Output = SignedTime;
+
+    fn sub(self, other: SignedTime) -> SignedTime {
+        Self::from_milliseconds(self.milliseconds.saturating_sub(other.milliseconds))
+    }
+}
+
+impl Neg for SignedTime {
+    type Output = SignedTime;
+
+    fn neg(self) -> SignedTime {
+        Self::from_milliseconds(self.milliseconds.saturating_neg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_time_formats_negative() {
+        let ahead = SignedTime::negative(Time::from_minutes(5));
+        assert_eq!("-00:05:00.000", ahead.to_string());
+        assert!(ahead.is_negative());
+        assert_eq!(Time::from_minutes(5), ahead.abs());
+        assert_eq!("00:05:00.000", (-ahead).to_string());
+        assert_eq!("00:00:00.000", SignedTime::default().to_string());
+    }
+
+    #[test]
+    fn test_signed_time_adds_negative_to_positive() {
+        let late = SignedTime::positive(Time::from_minutes(2));
+        let ahead = SignedTime::negative(Time::from_minutes(5));
+        assert_eq!(SignedTime::from_milliseconds(-180_000), late + ahead);
+        assert_eq!(SignedTime::from_milliseconds(420_000), late - ahead);
+        assert_eq!(
+            SignedTime::positive(Time::from_minutes(1)),
+            ahead + SignedTime::from(Time::from_minutes(6))
+        );
+    }
+}
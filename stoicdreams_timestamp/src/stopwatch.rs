@@ -8,6 +8,10 @@ pub type StopWatchUnit = u128;
 ///
 /// Expected usage is to use one of the following methods to create a new StopWatch struct:
 /// - `StopWatch::start()`
+/// - `StopWatch::from_start_nanoseconds(start)` - to resume timing from a previously stored `start_nanoseconds()` value.
+///
+/// Each `ellapsed_*` method reads the clock independently, so two calls describe different instants.
+/// Use `StopWatch::snapshot()` to read the clock once and derive every unit from the same `PreciseTime`.
 ///
 /// Example:
 /// ```
@@ -46,8 +50,84 @@ impl StopWatch {
         Self { start }
     }
 
+    /// Create a `ScopeTimer` guard that prints `label: <elapsed>` when it is dropped.
+    pub fn scope(label: &str) -> ScopeTimer {
+        ScopeTimer::new(label)
+    }
+
+    /// Create a StopWatch from a start time in nanoseconds since the Unix epoch, as returned by `start_nanoseconds()`.
+    pub fn from_start_nanoseconds(start: StopWatchUnit) -> Self {
+        Self { start }
+    }
+
+    /// Returns the start time in nanoseconds since the Unix epoch, which can be stored and later restored with `from_start_nanoseconds()`.
+    pub fn start_nanoseconds(&self) -> StopWatchUnit {
+        self.start
+    }
+
     pub fn ellapsed(&self) -> PreciseTime {
-        PreciseTime::from_nanoseconds(self.ellapsed_nanoseconds())
+        self.snapshot()
+    }
+
+    /// Reads the clock once and returns the time since start, so every unit derived from the result is consistent.
+    pub fn snapshot(&self) -> PreciseTime {
+        self.snapshot_at(now_nanoseconds())
+    }
+
+    /// Returns the time between start and `now` (nanoseconds since the Unix epoch).
+    pub fn snapshot_at(&self, now: StopWatchUnit) -> PreciseTime {
+        PreciseTime::from_nanoseconds(now.saturating_sub(self.start))
+    }
+
+    /// Returns `"label: <elapsed>"` for benchmark output, with the elapsed time formatted by `PreciseTime::format_auto()`.
+    /// e.g. `"db query: 1.23 ms"`
+    pub fn report(&self, label: &str) -> String {
+        self.report_at(label, now_nanoseconds())
+    }
+
+    /// Returns `"label: <elapsed>"` for the time between start and `now` (nanoseconds since the Unix epoch).
+    pub fn report_at(&self, label: &str, now: StopWatchUnit) -> String {
+        format!("{}: {}", label, self.snapshot_at(now).format_auto())
+    }
+
+    /// Returns true when at least `min` milliseconds have passed since start.
+    pub fn elapsed_at_least(&self, min: Time) -> bool {
+        self.elapsed_at_least_at(min, now_nanoseconds())
+    }
+
+    /// Returns true when at least `min` milliseconds passed between start and `now` (nanoseconds since the Unix epoch).
+    pub fn elapsed_at_least_at(&self, min: Time, now: StopWatchUnit) -> bool {
+        self.snapshot_at(now).to_milliseconds() >= min.to_milliseconds() as StopWatchUnit
+    }
+
+    /// Returns true when no more than `max` milliseconds have passed since start.
+    pub fn elapsed_at_most(&self, max: Time) -> bool {
+        self.elapsed_at_most_at(max, now_nanoseconds())
+    }
+
+    /// Returns true when no more than `max` milliseconds passed between start and `now` (nanoseconds since the Unix epoch).
+    pub fn elapsed_at_most_at(&self, max: Time, now: StopWatchUnit) -> bool {
+        self.snapshot_at(now).to_milliseconds() <= max.to_milliseconds() as StopWatchUnit
+    }
+
+    /// Returns the time since start as `(hours, minutes, seconds, milliseconds, microseconds, nanoseconds)`.
+    /// The clock is read once, so all of the components describe the same instant.
+    pub fn elapsed_components(&self) -> (u64, u8, u8, u16, u16, u16) {
+        self.elapsed_components_at(now_nanoseconds())
+    }
+
+    /// Returns the time between start and `now` (nanoseconds since the Unix epoch) as
+    /// `(hours, minutes, seconds, milliseconds, microseconds, nanoseconds)`.
+    pub fn elapsed_components_at(&self, now: StopWatchUnit) -> (u64, u8, u8, u16, u16, u16) {
+        let nanoseconds = now.saturating_sub(self.start);
+        (
+            (nanoseconds / (60 * 60 * 1000000000)) as u64,
+            ((nanoseconds / (60 * 1000000000)) % 60) as u8,
+            ((nanoseconds / 1000000000) % 60) as u8,
+            ((nanoseconds / 1000000) % 1000) as u16,
+            ((nanoseconds / 1000) % 1000) as u16,
+            (nanoseconds % 1000) as u16,
+        )
     }
 
     /// Returns the total number of nanoseconds since start.
@@ -97,4 +177,91 @@ mod tests {
         let utc_formatted = "00:00:00.000000000";
         assert_ne!(utc_formatted, ellapsed.format());
     }
+
+    #[test]
+    fn test_stopwatch_from_start_nanoseconds() {
+        let start = now_nanoseconds() - 90 * 1_000_000_000;
+        let stopwatch = StopWatch::from_start_nanoseconds(start);
+        assert_eq!(start, stopwatch.start_nanoseconds());
+        assert!(stopwatch.ellapsed_seconds() >= 90);
+        assert_eq!(1, stopwatch.ellapsed_minutes());
+    }
+
+    #[test]
+    fn test_stopwatch_elapsed_components_from_injected_clock() {
+        let stopwatch = StopWatch::from_start_nanoseconds(1_000);
+        let now = 1_000 + 3_723_004_005_006;
+        let (hours, minutes, seconds, milliseconds, microseconds, nanoseconds) =
+            stopwatch.elapsed_components_at(now);
+        assert_eq!(
+            (1, 2, 3, 4, 5, 6),
+            (
+                hours,
+                minutes,
+                seconds,
+                milliseconds,
+                microseconds,
+                nanoseconds
+            )
+        );
+        let total = ((((hours as u128 * 60 + minutes as u128) * 60 + seconds as u128) * 1000
+            + milliseconds as u128)
+            * 1000
+            + microseconds as u128)
+            * 1000
+            + nanoseconds as u128;
+        assert_eq!(now - stopwatch.start_nanoseconds(), total);
+    }
+
+    #[test]
+    fn test_stopwatch_elapsed_components_large_hours() {
+        let stopwatch = StopWatch::from_start_nanoseconds(0);
+        let now = 50 * 60 * 60 * 1_000_000_000;
+        assert_eq!((50, 0, 0, 0, 0, 0), stopwatch.elapsed_components_at(now));
+    }
+
+    #[test]
+    fn test_stopwatch_snapshot_is_consistent() {
+        let stopwatch = StopWatch::from_start_nanoseconds(5_000);
+        let snapshot = stopwatch.snapshot_at(5_000 + 61_234_567_891);
+        assert_eq!(61_234_567_891, snapshot.to_nanoseconds());
+        assert_eq!(61_234_567, snapshot.to_microseconds());
+        assert_eq!(61_234, snapshot.to_milliseconds());
+        assert_eq!(61, snapshot.to_seconds());
+        assert_eq!(1, snapshot.to_minutes());
+        assert_eq!("00:01:01.234567891", snapshot.format());
+        assert_eq!(
+            (0, 1, 1, 234, 567, 891),
+            stopwatch.elapsed_components_at(5_000 + 61_234_567_891)
+        );
+    }
+
+    #[test]
+    fn test_stopwatch_elapsed_bounds_from_injected_clock() {
+        let stopwatch = StopWatch::from_start_nanoseconds(1_000_000_000);
+        let now = 1_000_000_000 + 1_500_000_000;
+        assert!(stopwatch.elapsed_at_least_at(Time::from_seconds(1), now));
+        assert!(stopwatch.elapsed_at_least_at(Time::from_milliseconds(1_500), now));
+        assert!(!stopwatch.elapsed_at_least_at(Time::from_seconds(2), now));
+        assert!(stopwatch.elapsed_at_most_at(Time::from_seconds(2), now));
+        assert!(stopwatch.elapsed_at_most_at(Time::from_milliseconds(1_500), now));
+        assert!(!stopwatch.elapsed_at_most_at(Time::from_seconds(1), now));
+        assert!(StopWatch::start().elapsed_at_most(Time::from_hours(1)));
+    }
+
+    #[test]
+    fn test_stopwatch_report() {
+        let stopwatch = StopWatch::from_start_nanoseconds(1_000);
+        assert_eq!(
+            "db query: 1.23 ms",
+            stopwatch.report_at("db query", 1_000 + 1_230_000)
+        );
+        assert_eq!(
+            "parse: 2 s",
+            stopwatch.report_at("parse", 1_000 + 2_000_000_000)
+        );
+        let report = StopWatch::start().report("startup");
+        assert!(report.starts_with("startup: "));
+        assert!(report.ends_with('s'));
+    }
 }
@@ -1,16 +1,21 @@
+use crate::parser::duration_nanoseconds;
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 pub type TimeUnit = u64;
 
 /// A time struct that can be used to represent a time in milliseconds.
 /// Expected usage is to use one of the following methods to create a new Time struct:
 /// - `Time::new(days, hours, minutes, seconds)`
+/// - `Time::from_weeks(weeks)`
 /// - `Time::from_days(days)`
 /// - `Time::from_hours(hours)`
 /// - `Time::from_minutes(minutes)`
 /// - `Time::from_seconds(seconds)`
 /// - `Time::from_milliseconds(milliseconds)`
+/// - `Time::from_duration_lossy(duration)` - truncating anything finer than a millisecond
+/// - `Time::from_format(value)` - to parse the output of `Time::format()`
 ///
 /// Example:
 /// ```
@@ -83,12 +88,15 @@ pub type TimeUnit = u64;
 /// assert_eq!(time.get_hour_of_day(), 0);
 /// assert_eq!(time.format(), "00:00:00.001");
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Time {
     milliseconds: TimeUnit,
 }
 
 impl Time {
+    /// A time of zero milliseconds.
+    pub const ZERO: Time = Time { milliseconds: 0 };
+
     pub fn new(days: u16, hours: u8, minutes: u8, seconds: u8) -> Self {
         let seconds = (days as TimeUnit * 24 * 60 * 60)
             + (hours as TimeUnit * 60 * 60)
@@ -98,6 +106,15 @@ impl Time {
         Self { milliseconds }
     }
 
+    pub fn from_weeks(weeks: TimeUnit) -> Self {
+        let days = weeks * 7;
+        let hours = days * 24;
+        let minutes = hours * 60;
+        let seconds = minutes * 60;
+        let milliseconds = seconds * 1000;
+        Self { milliseconds }
+    }
+
     pub fn from_days(days: u16) -> Self {
         let hours = days as TimeUnit * 24;
         let minutes = hours * 60;
@@ -128,6 +145,23 @@ impl Time {
         Self { milliseconds }
     }
 
+    /// Create a new `Time` from a `std::time::Duration`, truncating anything finer than a millisecond
+    /// (e.g. 1.5 ms becomes 1 ms). Durations longer than `u64::MAX` milliseconds saturate.
+    pub fn from_duration_lossy(duration: Duration) -> Self {
+        Self::from_milliseconds(TimeUnit::try_from(duration.as_millis()).unwrap_or(TimeUnit::MAX))
+    }
+
+    /// Parses a `Time` from the layouts produced by `Time::format()`:
+    /// `"HH:MM:SS"`, `"HH:MM:SS.mmm"`, and `"D HH:MM:SS.mmm"` with a leading number of days.
+    ///
+    /// Without a days prefix hours may exceed 23, so the output of `Time::format_hms_overflow()` is accepted as well.
+    pub fn from_format(value: &str) -> Result<Time, ParseError> {
+        let nanoseconds = duration_nanoseconds(value, 3, "3 digit milliseconds")?;
+        let milliseconds = TimeUnit::try_from(nanoseconds / 1_000_000)
+            .map_err(|_| ParseError::Overflow { field: "duration" })?;
+        Ok(Self { milliseconds })
+    }
+
     pub fn get_milliseconds_of_second(&self) -> u16 {
         (self.milliseconds % 1000) as u16
     }
@@ -148,11 +182,28 @@ impl Time {
         time_format(self.milliseconds as DateTimeUnit, "%D %H:%M:%S.%f")
     }
 
+    /// Formats this time as `HH:MM:SS.fff` without a days segment, so hours accumulate past 24.
+    /// e.g. 50 hours formats as `"50:00:00.000"`
+    pub fn format_hms_overflow(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}.{:03}",
+            self.to_hours(),
+            self.get_minutes_of_hour(),
+            self.get_seconds_of_minute(),
+            self.get_milliseconds_of_second()
+        )
+    }
+
     /// Returns the total number of milliseconds.
     pub fn to_milliseconds(&self) -> TimeUnit {
         self.milliseconds
     }
 
+    /// Returns this time as a `std::time::Duration`, which is always exact.
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_millis(self.milliseconds)
+    }
+
     /// Returns the total number of seconds.
     pub fn to_seconds(&self) -> TimeUnit {
         self.milliseconds / 1000
@@ -172,6 +223,40 @@ impl Time {
     pub fn to_days(&self) -> TimeUnit {
         self.milliseconds / (24 * 60 * 60 * 1000)
     }
+
+    /// Returns the total number of whole weeks.
+    pub fn to_weeks(&self) -> TimeUnit {
+        self.milliseconds / (7 * 24 * 60 * 60 * 1000)
+    }
+
+    /// Returns true when this time is zero milliseconds long.
+    pub fn is_zero(&self) -> bool {
+        self.milliseconds == 0
+    }
+
+    /// Returns true when this time is longer than `other`.
+    pub fn is_longer_than(&self, other: &Time) -> bool {
+        self > other
+    }
+
+    /// Returns true when this time is shorter than `other`.
+    pub fn is_shorter_than(&self, other: &Time) -> bool {
+        self < other
+    }
+
+    /// Returns this time minus `other`, floored at zero instead of underflowing.
+    pub fn saturating_sub(&self, other: &Time) -> Time {
+        Self {
+            milliseconds: self.milliseconds.saturating_sub(other.milliseconds),
+        }
+    }
+
+    /// Returns the difference in milliseconds between this time and `other`, which is negative when `other` is longer.
+    /// Differences beyond the range of an `i64` are saturated to `i64::MIN` or `i64::MAX`.
+    pub fn signed_diff(&self, other: &Time) -> i64 {
+        let difference = self.milliseconds as i128 - other.milliseconds as i128;
+        difference.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
 }
 
 #[cfg(test)]
@@ -197,4 +282,145 @@ mod tests {
         assert_eq!(49, time.get_minutes_of_hour());
         assert_eq!(27, time.get_seconds_of_minute());
     }
+
+    #[test]
+    fn test_time_saturating_sub() {
+        let short = Time::from_minutes(5);
+        let long = Time::from_minutes(30);
+        assert_eq!(0, short.saturating_sub(&long).to_milliseconds());
+        assert_eq!(25, long.saturating_sub(&short).to_minutes());
+    }
+
+    #[test]
+    fn test_time_signed_diff() {
+        let short = Time::from_minutes(5);
+        let long = Time::from_minutes(30);
+        assert_eq!(-1_500_000, short.signed_diff(&long));
+        assert_eq!(1_500_000, long.signed_diff(&short));
+        assert_eq!(0, short.signed_diff(&short));
+        let longest = Time::from_milliseconds(u64::MAX);
+        assert_eq!(i64::MAX, longest.signed_diff(&Time::ZERO));
+        assert_eq!(i64::MIN, Time::ZERO.signed_diff(&longest));
+        assert_eq!(0, longest.signed_diff(&longest));
+        let middle = Time::from_milliseconds(i64::MAX as u64);
+        assert_eq!(i64::MAX, middle.signed_diff(&Time::ZERO));
+        assert_eq!(-i64::MAX, Time::ZERO.signed_diff(&middle));
+    }
+
+    #[test]
+    fn test_time_comparisons() {
+        let hour = Time::from_hours(1);
+        let half_hour = Time::from_minutes(30);
+        assert!(hour.is_longer_than(&half_hour));
+        assert!(!hour.is_shorter_than(&half_hour));
+        assert!(half_hour.is_shorter_than(&hour));
+        assert!(!half_hour.is_longer_than(&hour));
+        assert!(!hour.is_longer_than(&Time::from_minutes(60)));
+        assert!(hour > half_hour);
+        assert_eq!(hour, Time::from_minutes(60));
+        assert_eq!(
+            Some(&half_hour),
+            [hour.clone(), half_hour.clone()].iter().min()
+        );
+    }
+
+    #[test]
+    fn test_time_format_hms_overflow() {
+        assert_eq!("50:00:00.000", Time::from_hours(50).format_hms_overflow());
+        assert_eq!(
+            "00:00:00.000",
+            Time::from_milliseconds(0).format_hms_overflow()
+        );
+        let time = Time::new(2, 1, 27, 3);
+        assert_eq!("49:27:03.000", time.format_hms_overflow());
+        assert_eq!("2 01:27:03.000", time.format());
+    }
+
+    #[test]
+    fn test_time_weeks() {
+        let time = Time::from_weeks(2);
+        assert_eq!(14, time.to_days());
+        assert_eq!(2, time.to_weeks());
+        assert_eq!(1, Time::from_days(13).to_weeks());
+        assert_eq!("14 00:00:00.000", time.format());
+    }
+
+    #[test]
+    fn test_time_from_format() {
+        assert_eq!(
+            Err(ParseError::Overflow { field: "duration" }),
+            Time::from_format("5124095576030432:00:00.000")
+        );
+        assert_eq!(
+            Err(ParseError::Overflow { field: "duration" }),
+            Time::from_format("99999999999999999999999999999999999999999:00:00")
+        );
+        assert_eq!(Ok(Time::new(0, 2, 49, 27)), Time::from_format("02:49:27"));
+        assert_eq!(
+            Ok(Time::from_milliseconds(10_167_295)),
+            Time::from_format("02:49:27.295")
+        );
+        assert_eq!(
+            Ok(Time::from_milliseconds(5_504_294_967_295)),
+            Time::from_format("63707 02:49:27.295")
+        );
+        assert_eq!(Ok(Time::from_hours(50)), Time::from_format("50:00:00.000"));
+        for time in [
+            Time::from_milliseconds(0),
+            Time::from_milliseconds(5_504_294_967_295),
+            Time::new(1, 0, 0, 1),
+            Time::from_milliseconds(DateTimeUnit::MAX as TimeUnit),
+        ] {
+            assert_eq!(Ok(time.clone()), Time::from_format(&time.format()));
+            assert_eq!(
+                Ok(time.clone()),
+                Time::from_format(&time.format_hms_overflow())
+            );
+        }
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "hour",
+                value: 24
+            }),
+            Time::from_format("1 24:00:00.000")
+        );
+        assert_eq!(
+            Err(ParseError::OutOfRange {
+                field: "minute",
+                value: 60
+            }),
+            Time::from_format("00:60:00")
+        );
+        assert_eq!(
+            Err(ParseError::Unexpected {
+                position: 10,
+                expected: "3 digit milliseconds"
+            }),
+            Time::from_format("00:00:00.5s")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingInput { position: 8 }),
+            Time::from_format("00:00:00 ")
+        );
+    }
+
+    #[test]
+    fn test_time_duration_conversions() {
+        let time = Time::from_duration_lossy(Duration::from_nanos(1_500_000));
+        assert_eq!(Time::from_milliseconds(1), time);
+        assert_eq!(Duration::from_millis(1), time.as_duration());
+        let time = Time::new(1, 2, 3, 4);
+        assert_eq!(time, Time::from_duration_lossy(time.as_duration()));
+        assert_eq!(
+            Time::from_milliseconds(u64::MAX),
+            Time::from_duration_lossy(Duration::MAX)
+        );
+    }
+
+    #[test]
+    fn test_time_is_zero() {
+        assert!(Time::ZERO.is_zero());
+        assert!(Time::new(0, 0, 0, 0).is_zero());
+        assert!(!Time::from_milliseconds(1).is_zero());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::fmt::Write;
 
 pub(crate) const EPOCH_START: DateTimeUnit = 62_167_132_800_000;
 
@@ -6,182 +7,505 @@ pub fn is_leap_year(year: u16) -> bool {
     year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
 }
 
+/// Returns true when the year has a February 29th in the `DateTime` calendar.
+///
+/// Year 0 is the first year of the `DateTime` epoch and is counted as a common year of 365 days, even though
+/// `is_leap_year(0)` is true, which keeps every date from AD 1 onwards aligned with the Gregorian calendar.
+pub(crate) fn has_leap_day(year: u16) -> bool {
+    year > 0 && is_leap_year(year)
+}
+
+/// Returns the number of days in the month (1 = January, 12 = December) of the given year, or 0 for an invalid month.
+/// February of year 0 has 28 days, as the `DateTime` calendar counts year 0 as a common year.
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if has_leap_day(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
 /// Formats a timestamp in milliseconds since the Unix epoch (Midnight of Jan 1st, 1970) into a
 /// human readable format.
-/// format options:
-/// %Y = year
-/// %m = month
-/// %D = days
-/// %d = day of month
-/// %H = hour
-/// %M = minute
-/// %S = second
-/// %f = milliseconds
+/// See `time_format()` for the format options.
 pub fn time_format_from_unix_epoch(milliseconds_since_epoch: DateTimeUnit, format: &str) -> String {
     time_format(EPOCH_START + milliseconds_since_epoch, format)
 }
 
 /// Formats a timestamp in milliseconds since 0 AD into a human readable format.
 /// format options:
-/// %Y = 4 digit year
+/// %Y = 4 digit zero-padded year (e.g. `0044`)
+/// %-Y = year without padding (e.g. `44`)
+/// %C = 2 digit century, the year divided by 100 (e.g. `20` for 2023)
+/// %y = 2 digit year within the century (e.g. `05` for 2005)
 /// %m = 2 digit month
 /// %D = N digit days when greater than 0
 /// %d = 2 digit day of month
 /// %H = 2 digit hour
 /// %M = 2 digit minute
 /// %S = 2 digit second
-/// %f = 2 digit milliseconds
+/// %u = 1 digit ISO day of the week, where 1 is Monday and 7 is Sunday (same as `date +%u`)
+/// %A = English weekday name (e.g. `Sunday`)
+/// %a = three letter English weekday name (e.g. `Sun`)
+/// %B = English month name (e.g. `May`)
+/// %b = three letter English month name (e.g. `Jan`)
+/// %f = 3 digit milliseconds
+/// %.f = `.` followed by 1 to 3 digit milliseconds without trailing zeros (e.g. `.5`), or nothing when milliseconds are zero
+/// %,f = `,` followed by 3 digit milliseconds, the decimal comma permitted by ISO 8601 (e.g. `14:36:46,076`)
+/// %% = a literal `%`
+///
+/// Text within single quotes is copied as is, so `'50%'` formats as `50%`, and two single quotes (`''`) format as one.
+/// Any other text, including unknown `%` sequences, is copied as is.
 pub fn time_format(milliseconds_since_ad_zero: DateTimeUnit, format: &str) -> String {
     let date_time = DateTime::from_milliseconds(milliseconds_since_ad_zero);
-    let mut format = format.to_string();
-    if format.contains("%Y") {
-        let year = date_time.get_year();
-        format = format.replace("%Y", &year.to_string());
-    }
-    if format.contains("%m") {
-        let month = date_time.get_month();
-        let month = if month < 10 {
-            format!("0{}", month)
-        } else {
-            month.to_string()
-        };
-        format = format.replace("%m", &month);
-    }
-    if format.contains("%D") {
-        let day = date_time.to_days();
-        if day > 0 {
-            format = format.replace("%D", &day.to_string());
-        } else {
-            format = format.replace("%D", "").trim().to_string();
-        }
-    }
-    if format.contains("%d") {
-        let day = date_time.get_day_of_month();
-        let day = if day < 10 {
-            format!("0{}", day)
-        } else {
-            day.to_string()
-        };
-        format = format.replace("%d", &day);
-    }
-    if format.contains("%H") {
-        let hour = date_time.get_hour_of_day();
-        let hour = if hour < 10 {
-            format!("0{}", hour)
-        } else {
-            hour.to_string()
-        };
-        format = format.replace("%H", &hour);
-    }
-    if format.contains("%M") {
-        let minute = date_time.get_minutes_of_hour();
-        let minute = if minute < 10 {
-            format!("0{}", minute)
-        } else {
-            minute.to_string()
-        };
-        format = format.replace("%M", &minute);
-    }
-    if format.contains("%S") {
-        let second = date_time.get_seconds_of_minute();
-        let second = if second < 10 {
-            format!("0{}", second)
-        } else {
-            second.to_string()
-        };
-        format = format.replace("%S", &second);
-    }
-    if format.contains("%f") {
-        let millisecond = date_time.get_milliseconds_of_second();
-        let millisecond = if millisecond < 10 {
-            format!("00{}", millisecond)
-        } else if millisecond < 100 {
-            format!("0{}", millisecond)
-        } else {
-            millisecond.to_string()
-        };
-        format = format.replace("%f", &millisecond);
-    }
-    format
+    let milliseconds = date_time.get_milliseconds_of_second() as u32;
+    format_with_fraction(&date_time, format, milliseconds, 3)
 }
 
 /// Formats a timestamp in nanoseconds since 0 AD into a human readable format.
-/// format options:
-/// %Y = 4 digit year
-/// %m = 2 digit month
-/// %D = N digit days when greater than 0
-/// %d = 2 digit day of month
-/// %H = 2 digit hour
-/// %M = 2 digit minute
-/// %S = 2 digit second
-/// %f = 2 digit nanoseconds
+/// Supports the same format options as `time_format()`, except that the fraction of a second is in nanoseconds:
+/// %f = 9 digit nanoseconds
+/// %.f = `.` followed by 1 to 9 digit nanoseconds without trailing zeros, or nothing when nanoseconds are zero
+/// %,f = `,` followed by 9 digit nanoseconds
 pub fn precise_time_format(nanoseconds: PreciseTimeUnit, format: &str) -> String {
     let milliseconds = (nanoseconds / 1_000_000) as DateTimeUnit;
     let date_time = DateTime::from_milliseconds(milliseconds);
-    let mut format = format.to_string();
-    if format.contains("%Y") {
-        let year = date_time.get_year();
-        format = format.replace("%Y", &year.to_string());
-    }
-    if format.contains("%m") {
-        let month = date_time.get_month();
-        let month = if month < 10 {
-            format!("0{}", month)
-        } else {
-            month.to_string()
-        };
-        format = format.replace("%m", &month);
-    }
-    if format.contains("%D") {
-        let day = date_time.to_days();
-        if day > 0 {
-            format = format.replace("%D", &day.to_string());
-        } else {
-            format = format.replace("%D", "").trim().to_string();
+    let nanoseconds = (nanoseconds % 1_000_000_000) as u32;
+    format_with_fraction(&date_time, format, nanoseconds, 9)
+}
+
+/// A piece of a format string, either literal text or a `%` field specifier.
+enum Token {
+    Literal(char),
+    Field(Specifier),
+}
+
+/// The `%` field specifiers supported by `time_format()`.
+#[derive(Clone, Copy)]
+pub(crate) enum Specifier {
+    Year,
+    UnpaddedYear,
+    Century,
+    TwoDigitYear,
+    Month,
+    Days,
+    DayOfMonth,
+    Hour,
+    Minute,
+    Second,
+    IsoWeekday,
+    WeekdayName,
+    WeekdayShortName,
+    MonthName,
+    MonthShortName,
+    Fraction,
+    TrimmedFraction,
+    CommaFraction,
+}
+
+impl Specifier {
+    fn kind(self) -> FieldKind {
+        match self {
+            Specifier::Year | Specifier::UnpaddedYear | Specifier::TwoDigitYear => FieldKind::Year,
+            Specifier::Century => FieldKind::Century,
+            Specifier::Month => FieldKind::Month,
+            Specifier::MonthName | Specifier::MonthShortName => FieldKind::MonthName,
+            Specifier::Days => FieldKind::Days,
+            Specifier::DayOfMonth => FieldKind::Day,
+            Specifier::Hour => FieldKind::Hour,
+            Specifier::Minute => FieldKind::Minute,
+            Specifier::Second => FieldKind::Second,
+            Specifier::IsoWeekday => FieldKind::Weekday,
+            Specifier::WeekdayName | Specifier::WeekdayShortName => FieldKind::WeekdayName,
+            Specifier::Fraction | Specifier::TrimmedFraction | Specifier::CommaFraction => {
+                FieldKind::Fraction
+            }
         }
     }
-    if format.contains("%d") {
-        let day = date_time.get_day_of_month();
-        let day = if day < 10 {
-            format!("0{}", day)
-        } else {
-            day.to_string()
-        };
-        format = format.replace("%d", &day);
-    }
-    if format.contains("%H") {
-        let hour = date_time.get_hour_of_day();
-        let hour = if hour < 10 {
-            format!("0{}", hour)
-        } else {
-            hour.to_string()
-        };
-        format = format.replace("%H", &hour);
-    }
-    if format.contains("%M") {
-        let minute = date_time.get_minutes_of_hour();
-        let minute = if minute < 10 {
-            format!("0{}", minute)
-        } else {
-            minute.to_string()
-        };
-        format = format.replace("%M", &minute);
-    }
-    if format.contains("%S") {
-        let second = date_time.get_seconds_of_minute();
-        let second = if second < 10 {
-            format!("0{}", second)
-        } else {
-            second.to_string()
+}
+
+/// Scans the `format` in a single pass, passing each literal character and field specifier to `emit`.
+fn scan_format(format: &str, mut emit: impl FnMut(Token)) {
+    let mut characters = format.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character == '\'' {
+            if characters.next_if_eq(&'\'').is_some() {
+                emit(Token::Literal('\''));
+                continue;
+            }
+            while let Some(character) = characters.next() {
+                if character != '\'' {
+                    emit(Token::Literal(character));
+                } else if characters.next_if_eq(&'\'').is_some() {
+                    emit(Token::Literal('\''));
+                } else {
+                    break;
+                }
+            }
+            continue;
+        }
+        if character != '%' {
+            emit(Token::Literal(character));
+            continue;
+        }
+        let specifier = characters.peek().copied();
+        let following = characters.clone().nth(1);
+        let token = match (specifier, following) {
+            (Some('%'), _) => Token::Literal('%'),
+            (Some('Y'), _) => Token::Field(Specifier::Year),
+            (Some('-'), Some('Y')) => {
+                characters.next();
+                Token::Field(Specifier::UnpaddedYear)
+            }
+            (Some('C'), _) => Token::Field(Specifier::Century),
+            (Some('y'), _) => Token::Field(Specifier::TwoDigitYear),
+            (Some('m'), _) => Token::Field(Specifier::Month),
+            (Some('D'), _) => Token::Field(Specifier::Days),
+            (Some('d'), _) => Token::Field(Specifier::DayOfMonth),
+            (Some('H'), _) => Token::Field(Specifier::Hour),
+            (Some('M'), _) => Token::Field(Specifier::Minute),
+            (Some('S'), _) => Token::Field(Specifier::Second),
+            (Some('u'), _) => Token::Field(Specifier::IsoWeekday),
+            (Some('A'), _) => Token::Field(Specifier::WeekdayName),
+            (Some('a'), _) => Token::Field(Specifier::WeekdayShortName),
+            (Some('B'), _) => Token::Field(Specifier::MonthName),
+            (Some('b'), _) => Token::Field(Specifier::MonthShortName),
+            (Some('f'), _) => Token::Field(Specifier::Fraction),
+            (Some('.'), Some('f')) => {
+                characters.next();
+                Token::Field(Specifier::TrimmedFraction)
+            }
+            (Some(','), Some('f')) => {
+                characters.next();
+                Token::Field(Specifier::CommaFraction)
+            }
+            _ => {
+                emit(Token::Literal('%'));
+                continue;
+            }
         };
-        format = format.replace("%S", &second);
+        characters.next();
+        emit(token);
     }
-    if format.contains("%f") {
-        let mut nanoseconds_display = (nanoseconds % 1_000_000_000).to_string();
-        while nanoseconds_display.len() < 9 {
-            nanoseconds_display = format!("0{}", nanoseconds_display);
+}
+
+/// Writes the value of a field `specifier` for the `date_time` to the `output`, where the fraction fields write the
+/// `fraction` of a second zero-padded to `fraction_digits` digits.
+fn write_field(
+    output: &mut String,
+    specifier: Specifier,
+    date_time: &DateTime,
+    fraction: u32,
+    fraction_digits: usize,
+) {
+    // Writing to a String cannot fail.
+    let _ = match specifier {
+        Specifier::Year => write!(output, "{:04}", date_time.get_year()),
+        Specifier::UnpaddedYear => write!(output, "{}", date_time.get_year()),
+        Specifier::Century => write!(output, "{:02}", date_time.get_year() / 100),
+        Specifier::TwoDigitYear => write!(output, "{:02}", date_time.get_year() % 100),
+        Specifier::Month => write!(output, "{:02}", date_time.get_month()),
+        Specifier::Days => match date_time.to_days() {
+            0 => Ok(()),
+            days => write!(output, "{}", days),
+        },
+        Specifier::DayOfMonth => write!(output, "{:02}", date_time.get_day_of_month()),
+        Specifier::Hour => write!(output, "{:02}", date_time.get_hour_of_day()),
+        Specifier::Minute => write!(output, "{:02}", date_time.get_minutes_of_hour()),
+        Specifier::Second => write!(output, "{:02}", date_time.get_seconds_of_minute()),
+        Specifier::IsoWeekday => write!(output, "{}", date_time.unix_weekday()),
+        Specifier::WeekdayName => write!(output, "{}", date_time.weekday().name()),
+        Specifier::WeekdayShortName => write!(output, "{}", date_time.weekday().short_name()),
+        Specifier::MonthName => write!(output, "{}", month_of(date_time).name()),
+        Specifier::MonthShortName => write!(output, "{}", month_of(date_time).short_name()),
+        Specifier::Fraction => write!(output, "{:0width$}", fraction, width = fraction_digits),
+        Specifier::CommaFraction => {
+            write!(output, ",{:0width$}", fraction, width = fraction_digits)
+        }
+        Specifier::TrimmedFraction => {
+            if fraction > 0 {
+                let digits = format!(".{:0width$}", fraction, width = fraction_digits);
+                output.push_str(digits.trim_end_matches('0'));
+            }
+            Ok(())
         }
-        format = format.replace("%f", &nanoseconds_display);
+    };
+}
+
+/// Formats the `date_time` with the `format`, where `%f` writes the `fraction` of a second
+/// zero-padded to `fraction_digits` digits.
+fn format_with_fraction(
+    date_time: &DateTime,
+    format: &str,
+    fraction: u32,
+    fraction_digits: usize,
+) -> String {
+    CompiledFormat::compile(format).format(date_time, fraction, fraction_digits)
+}
+
+/// A literal run of text or a field within a `CompiledFormat`.
+pub(crate) enum Segment {
+    Literal(String),
+    Field(Specifier),
+}
+
+/// A format scanned once into literal runs and fields, so formats used on hot paths (such as the layout of
+/// `DateTime::format()`) are not re-scanned on every call.
+pub(crate) struct CompiledFormat {
+    segments: Vec<Segment>,
+    capacity: usize,
+}
+
+impl CompiledFormat {
+    pub(crate) fn compile(format: &str) -> Self {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        scan_format(format, |token| match token {
+            Token::Literal(character) => literal.push(character),
+            Token::Field(specifier) => {
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(specifier));
+            }
+        });
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Self {
+            segments,
+            capacity: format.len() + 16,
+        }
+    }
+
+    /// Returns the literal runs and fields of the format in order, with quotes and `%%` already resolved to literal text.
+    pub(crate) fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Formats the `date_time`, where `%f` writes the `fraction` of a second zero-padded to `fraction_digits` digits.
+    /// When `%D` is empty the surrounding whitespace is trimmed.
+    pub(crate) fn format(
+        &self,
+        date_time: &DateTime,
+        fraction: u32,
+        fraction_digits: usize,
+    ) -> String {
+        let mut output = String::with_capacity(self.capacity);
+        let mut trim = false;
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => output.push_str(text),
+                Segment::Field(specifier) => {
+                    let length = output.len();
+                    write_field(
+                        &mut output,
+                        *specifier,
+                        date_time,
+                        fraction,
+                        fraction_digits,
+                    );
+                    trim |= matches!(specifier, Specifier::Days) && output.len() == length;
+                }
+            }
+        }
+        if trim {
+            return output.trim().to_string();
+        }
+        output
+    }
+}
+
+/// Splits the formatted `date_time` into literal text and fields, using the same format options as `time_format()`.
+/// Fields that format as empty text (`%D` with zero days, `%.f` with zero milliseconds) are left out.
+/// When `%D` is empty the surrounding whitespace is trimmed, the same as `time_format()`.
+pub(crate) fn format_parts(date_time: &DateTime, format: &str) -> Vec<FormatPart> {
+    let milliseconds = date_time.get_milliseconds_of_second() as u32;
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut trim = false;
+    scan_format(format, |token| match token {
+        Token::Literal(character) => literal.push(character),
+        Token::Field(specifier) => {
+            let mut text = String::new();
+            write_field(&mut text, specifier, date_time, milliseconds, 3);
+            if text.is_empty() {
+                trim |= matches!(specifier, Specifier::Days);
+                return;
+            }
+            if !literal.is_empty() {
+                parts.push(FormatPart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(FormatPart::Field {
+                kind: specifier.kind(),
+                text,
+            });
+        }
+    });
+    if !literal.is_empty() {
+        parts.push(FormatPart::Literal(literal));
+    }
+    if trim {
+        if let Some(FormatPart::Literal(text)) = parts.first_mut() {
+            *text = text.trim_start().to_string();
+        }
+        if let Some(FormatPart::Literal(text)) = parts.last_mut() {
+            *text = text.trim_end().to_string();
+        }
+        parts.retain(|part| !part.text().is_empty());
+    }
+    parts
+}
+
+/// Returns the `Month` of the `date_time`.
+fn month_of(date_time: &DateTime) -> Month {
+    Month::from_number(date_time.get_month()).unwrap_or(Month::January)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_format_auto_width_fraction() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let format = "%Y-%m-%dT%H:%M:%S%.fZ";
+        assert_eq!(
+            "2023-05-28T14:36:46Z",
+            time_format(datetime.to_milliseconds(), format)
+        );
+        assert_eq!(
+            "2023-05-28T14:36:46.5Z",
+            time_format(datetime.to_milliseconds() + 500, format)
+        );
+        assert_eq!(
+            "2023-05-28T14:36:46.076Z",
+            time_format(datetime.to_milliseconds() + 76, format)
+        );
+        assert_eq!(
+            "2023-05-28T14:36:46.12Z",
+            time_format(datetime.to_milliseconds() + 120, format)
+        );
+    }
+
+    #[test]
+    fn test_time_format_zero_padded_year() {
+        let early = DateTime::new(44, 3, 15, 0, 0, 0).to_milliseconds();
+        assert_eq!("0044-03-15", time_format(early, "%Y-%m-%d"));
+        assert_eq!("44-03-15", time_format(early, "%-Y-%m-%d"));
+        let recent = DateTime::new(2023, 5, 28, 0, 0, 0).to_milliseconds();
+        assert_eq!("2023-05-28", time_format(recent, "%Y-%m-%d"));
+        assert_eq!("2023", time_format(recent, "%-Y"));
+        assert_eq!(
+            "0044",
+            precise_time_format(early as PreciseTimeUnit * 1_000_000, "%Y")
+        );
+    }
+
+    #[test]
+    fn test_time_format_names() {
+        let name: &'static str = Weekday::Monday.name();
+        assert_eq!("Monday", name);
+        let name: &'static str = Month::May.short_name();
+        assert_eq!("May", name);
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46).to_milliseconds();
+        assert_eq!("Sunday, May 28 2023", time_format(datetime, "%A, %B %d %Y"));
+        let datetime = DateTime::new(2024, 1, 1, 0, 0, 0).to_milliseconds();
+        assert_eq!("Mon 01 Jan 2024", time_format(datetime, "%a %d %b %Y"));
+    }
+
+    #[test]
+    fn test_time_format_quoted_literals_and_percent() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46).to_milliseconds();
+        assert_eq!("50%", time_format(datetime, "'50%'"));
+        assert_eq!("It is %Y", time_format(datetime, "'It is %Y'"));
+        assert_eq!(
+            "Year 2023 is 100%",
+            time_format(datetime, "'Year' %Y 'is 100%'")
+        );
+        assert_eq!("%Y=2023", time_format(datetime, "%%Y=%Y"));
+        assert_eq!("14 o'clock", time_format(datetime, "%H 'o''clock'"));
+        assert_eq!("it's 2023", time_format(datetime, "it''s %Y"));
+        assert_eq!("100%", time_format(datetime, "100%"));
+        assert_eq!("%q", time_format(datetime, "%q"));
+        assert_eq!(
+            "00:00:01.5",
+            precise_time_format(1_500_000_000, "%D %H:%M:%S%.f")
+        );
+        assert_eq!(
+            "2 00:00:00.000000001",
+            precise_time_format(2 * 24 * 60 * 60 * 1_000_000_000 + 1, "%D %H:%M:%S.%f")
+        );
+    }
+
+    #[test]
+    fn test_time_format_century_and_two_digit_year() {
+        let recent = DateTime::new(2023, 5, 28, 0, 0, 0).to_milliseconds();
+        assert_eq!("20", time_format(recent, "%C"));
+        assert_eq!("23", time_format(recent, "%y"));
+        assert_eq!("2023", time_format(recent, "%C%y"));
+        let early = DateTime::new(7, 1, 1, 0, 0, 0).to_milliseconds();
+        assert_eq!("00", time_format(early, "%C"));
+        assert_eq!("07", time_format(early, "%y"));
+        let padded = DateTime::new(2005, 1, 1, 0, 0, 0).to_milliseconds();
+        assert_eq!("05/01/01", time_format(padded, "%y/%m/%d"));
+    }
+
+    #[test]
+    fn test_compiled_format_matches_time_format() {
+        let formats = [
+            "%Y-%m-%d %H:%M:%S.%f",
+            "'at' %H:%M%.f on %A, %B %d 50%%",
+            "%D %H:%M:%S.%f",
+            "",
+        ];
+        let datetimes = [
+            DateTime::new(2023, 5, 28, 14, 36, 46).to_milliseconds() + 76,
+            DateTime::new(44, 3, 15, 0, 0, 0).to_milliseconds(),
+            0,
+        ];
+        for format in formats {
+            let compiled = CompiledFormat::compile(format);
+            for milliseconds in datetimes {
+                let datetime = DateTime::from_milliseconds(milliseconds);
+                let fraction = datetime.get_milliseconds_of_second() as u32;
+                assert_eq!(
+                    time_format(milliseconds, format),
+                    compiled.format(&datetime, fraction, 3)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_format_decimal_comma() {
+        let datetime = DateTime::new(2023, 5, 28, 14, 36, 46).to_milliseconds() + 76;
+        assert_eq!("14:36:46,076", time_format(datetime, "%H:%M:%S%,f"));
+        assert_eq!(
+            "2023-05-28T14:36:46,000Z",
+            time_format(datetime - 76, "%Y-%m-%dT%H:%M:%S%,fZ")
+        );
+        assert_eq!(
+            "46,076000007",
+            precise_time_format(datetime as PreciseTimeUnit * 1_000_000 + 7, "%S%,f")
+        );
+        assert_eq!("1,5", time_format(datetime, "1,5"));
+    }
+
+    #[test]
+    fn test_is_leap_year_is_astronomical_for_year_0() {
+        assert!(is_leap_year(0));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert!(!has_leap_day(0));
+        assert!(has_leap_day(2000));
+        assert_eq!(28, days_in_month(0, 2));
+        assert_eq!(29, days_in_month(4, 2));
+        assert_eq!(
+            DateTime::new(0, 3, 1, 0, 0, 0),
+            DateTime::new(0, 2, 28, 0, 0, 0).add_days(1)
+        );
     }
-    format
 }
@@ -1,6 +1,6 @@
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::time::SystemTime;
+use std::cmp::Ordering;
 
 /// A time stamp object that stores the time of creation and the time of last update.
 /// Intended for use in data storage solutions that support storing complex data structures (e.g. [SurrealDB](https://surrealdb.com/).)
@@ -11,44 +11,63 @@ use std::time::SystemTime;
 ///
 /// Other methods include:
 /// - `TimeStamp::update()`
-/// - `TimeStamp::time_has_passed_since_last_update(time)`
-/// - `TimeStamp::time_has_passed_since_created(time)`
+/// - `TimeStamp::has_ttl_elapsed_since_update(ttl)`
+/// - `TimeStamp::has_ttl_elapsed_since_created(ttl)`
 /// - `TimeStamp::get_created()`
 /// - `TimeStamp::get_updated()`
+/// - `TimeStamp::created_datetime()`
+/// - `TimeStamp::updated_datetime()`
+/// - `TimeStamp::ttl_fraction(ttl, now)`
 ///
 /// Example:
 /// ```
 /// use stoicdreams_timestamp::prelude::*;
 ///
 /// let mut timestamp = TimeStamp::now();
-/// assert_eq!(timestamp.time_has_passed_since_last_update(Time::from_seconds(1)), false);
-/// assert_eq!(timestamp.time_has_passed_since_created(Time::from_seconds(1)), false);
+/// assert_eq!(timestamp.has_ttl_elapsed_since_update(Time::from_seconds(1)), false);
+/// assert_eq!(timestamp.has_ttl_elapsed_since_created(Time::from_seconds(1)), false);
 ///
 /// std::thread::sleep(std::time::Duration::from_secs(1));
 ///
 /// timestamp.update();
-/// assert_eq!(timestamp.time_has_passed_since_last_update(Time::from_seconds(1)), false);
-/// assert_eq!(timestamp.time_has_passed_since_created(Time::from_seconds(1)), true);
+/// assert_eq!(timestamp.has_ttl_elapsed_since_update(Time::from_seconds(1)), false);
+/// assert_eq!(timestamp.has_ttl_elapsed_since_created(Time::from_seconds(1)), true);
 ///
 /// std::thread::sleep(std::time::Duration::from_secs(1));
-/// assert_eq!(timestamp.time_has_passed_since_last_update(Time::from_seconds(1)), true);
+/// assert_eq!(timestamp.has_ttl_elapsed_since_update(Time::from_seconds(1)), true);
 ///
 /// let created_display = timestamp.get_created();
 /// let updated_display = timestamp.get_updated();
 /// assert_ne!(created_display, updated_display);
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+///
+/// Both fields are stored as milliseconds since the start of AD 0, the same base as `DateTime::to_milliseconds()`.
+/// Versions up to 0.1.2 stored `TimeStamp::now()` and `TimeStamp::update()` values as milliseconds since the Unix epoch,
+/// so records persisted by those versions should be loaded through `TimeStamp::from_unix_epoch_milliseconds(created, updated)`.
+///
+/// TimeStamps are ordered by their `updated` time, with ties broken by their `created` time,
+/// so sorting records is total and deterministic.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct TimeStamp {
+    /// Milliseconds since the start of AD 0 (see `DateTime::to_milliseconds()`).
+    /// Prefer `created_datetime()` for reading, which avoids mixing this up with Unix milliseconds.
     pub created: DateTimeUnit,
+    /// Milliseconds since the start of AD 0 (see `DateTime::to_milliseconds()`).
+    /// Prefer `updated_datetime()` for reading, which avoids mixing this up with Unix milliseconds.
     pub updated: DateTimeUnit,
 }
 
 /// Returns the current time in milliseconds since the Unix epoch (Midnight of Jan 1st, 1970).
+/// Alias of `DateTime::now_unix_millis()`.
 pub fn now_milliseconds() -> DateTimeUnit {
-    let now = SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .expect("system time before Unix epoch");
-    now.as_millis() as DateTimeUnit
+    DateTime::now_unix_millis()
+}
+
+/// Returns true when at least `ttl` has passed between `since` (milliseconds since AD 0) and now.
+fn has_ttl_elapsed(since: DateTimeUnit, ttl: Time) -> bool {
+    let now = DateTime::now().to_milliseconds();
+    let milliseconds = DateTimeUnit::try_from(ttl.to_milliseconds()).unwrap_or(DateTimeUnit::MAX);
+    since.saturating_add(milliseconds) <= now
 }
 
 impl Default for TimeStamp {
@@ -59,10 +78,7 @@ impl Default for TimeStamp {
 
 impl TimeStamp {
     pub fn now() -> Self {
-        Self {
-            created: now_milliseconds(),
-            updated: now_milliseconds(),
-        }
+        Self::from_datetime(DateTime::now())
     }
 
     pub fn from_datetime(time: DateTime) -> Self {
@@ -72,28 +88,48 @@ impl TimeStamp {
         }
     }
 
+    /// Creates a TimeStamp from `created` and `updated` values given in milliseconds since the Unix epoch.
+    /// Use this to migrate records written by `TimeStamp::now()` or `TimeStamp::update()` in versions up to 0.1.2.
+    pub fn from_unix_epoch_milliseconds(created: DateTimeUnit, updated: DateTimeUnit) -> Self {
+        Self {
+            created: created.saturating_add(EPOCH_START),
+            updated: updated.saturating_add(EPOCH_START),
+        }
+    }
+
     pub fn update(&mut self) {
-        self.updated = now_milliseconds();
+        self.updated = DateTime::now().to_milliseconds();
     }
 
+    #[deprecated(note = "use `has_ttl_elapsed_since_update`, which does not need `&mut self`")]
     pub fn time_has_passed_since_last_update(&mut self, time: Time) -> bool {
-        let now = now_milliseconds();
-        let updated = self.updated;
-        let milliseconds = time.to_milliseconds() as DateTimeUnit;
-        if updated + milliseconds <= now {
-            return true;
-        }
-        false
+        self.has_ttl_elapsed_since_update(time)
     }
 
+    #[deprecated(note = "use `has_ttl_elapsed_since_created`, which does not need `&mut self`")]
     pub fn time_has_passed_since_created(&mut self, time: Time) -> bool {
-        let now = now_milliseconds();
-        let created = self.created;
-        let milliseconds = time.to_milliseconds() as DateTimeUnit;
-        if created + milliseconds <= now {
-            return true;
+        self.has_ttl_elapsed_since_created(time)
+    }
+
+    /// Returns true when at least `ttl` has passed since the last update.
+    pub fn has_ttl_elapsed_since_update(&self, ttl: Time) -> bool {
+        has_ttl_elapsed(self.updated, ttl)
+    }
+
+    /// Returns true when at least `ttl` has passed since this TimeStamp was created.
+    pub fn has_ttl_elapsed_since_created(&self, ttl: Time) -> bool {
+        has_ttl_elapsed(self.created, ttl)
+    }
+
+    /// Returns how far through its `ttl` this TimeStamp is at `now`, measured from the last update.
+    /// The result is clamped to `0.0` (just updated) through `1.0` (expired), and a zero `ttl` is always expired.
+    pub fn ttl_fraction(&self, ttl: Time, now: DateTime) -> f64 {
+        let elapsed = now.to_milliseconds().saturating_sub(self.updated).max(0);
+        let ttl = ttl.to_milliseconds();
+        if ttl == 0 {
+            return 1.0;
         }
-        false
+        (elapsed as f64 / ttl as f64).clamp(0.0, 1.0)
     }
 
     pub fn get_created(&self) -> String {
@@ -103,6 +139,45 @@ impl TimeStamp {
     pub fn get_updated(&self) -> String {
         DateTime::from_milliseconds(self.updated).format()
     }
+
+    /// Returns the time of creation as a `DateTime`.
+    pub fn created_datetime(&self) -> DateTime {
+        DateTime::from_milliseconds(self.created)
+    }
+
+    /// Returns the time of the last update as a `DateTime`.
+    pub fn updated_datetime(&self) -> DateTime {
+        DateTime::from_milliseconds(self.updated)
+    }
+}
+
+/// Orders by `updated` first, then by `created` when the `updated` times are equal.
+impl Ord for TimeStamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.updated
+            .cmp(&other.updated)
+            .then_with(|| self.created.cmp(&other.created))
+    }
+}
+
+impl PartialOrd for TimeStamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A TimeStamp equals a `DateTime` when it was last `updated` at that time. The `created` time is not compared.
+impl PartialEq<DateTime> for TimeStamp {
+    fn eq(&self, other: &DateTime) -> bool {
+        self.updated == other.to_milliseconds()
+    }
+}
+
+/// TimeStamps are ordered against a `DateTime` by their `updated` time. The `created` time is not compared.
+impl PartialOrd<DateTime> for TimeStamp {
+    fn partial_cmp(&self, other: &DateTime) -> Option<Ordering> {
+        Some(self.updated.cmp(&other.to_milliseconds()))
+    }
 }
 
 #[cfg(test)]
@@ -119,4 +194,123 @@ mod tests {
         assert_eq!(1_685_284_606_076, timestamp.updated - EPOCH_START);
         assert_eq!(utc_formatted, timestamp.get_created());
     }
+
+    #[test]
+    fn test_timestamp_now_uses_datetime_base() {
+        let before = DateTime::now().to_milliseconds();
+        let timestamp = TimeStamp::now();
+        let after = DateTime::now().to_milliseconds();
+        assert!(before <= timestamp.created && timestamp.created <= after);
+        assert_eq!(timestamp.created, timestamp.updated);
+    }
+
+    #[test]
+    fn test_timestamp_ttl_fraction() {
+        let updated = DateTime::new(2023, 5, 28, 12, 0, 0);
+        let timestamp = TimeStamp::from_datetime(updated.clone());
+        let ttl = Time::from_minutes(10);
+        assert_eq!(0.0, timestamp.ttl_fraction(ttl.clone(), updated.clone()));
+        let halfway = DateTime::new(2023, 5, 28, 12, 5, 0);
+        assert_eq!(0.5, timestamp.ttl_fraction(ttl.clone(), halfway));
+        let expired = DateTime::new(2023, 5, 28, 13, 0, 0);
+        assert_eq!(1.0, timestamp.ttl_fraction(ttl.clone(), expired));
+        let before = DateTime::new(2023, 5, 28, 11, 0, 0);
+        assert_eq!(0.0, timestamp.ttl_fraction(ttl, before));
+        assert_eq!(
+            1.0,
+            timestamp.ttl_fraction(Time::from_milliseconds(0), updated)
+        );
+    }
+
+    #[test]
+    fn test_timestamp_compares_updated_to_datetime() {
+        let created = DateTime::new(2023, 5, 1, 0, 0, 0);
+        let updated = DateTime::new(2023, 5, 28, 14, 36, 46);
+        let mut timestamp = TimeStamp::from_datetime(created.clone());
+        timestamp.updated = updated.to_milliseconds();
+        assert_eq!(updated, timestamp.updated_datetime());
+        assert!(timestamp == updated);
+        assert!(timestamp != created);
+        assert!(timestamp > created);
+        assert!(timestamp < DateTime::new(2023, 5, 28, 14, 36, 47));
+        assert_eq!(Some(Ordering::Equal), timestamp.partial_cmp(&updated));
+    }
+
+    #[test]
+    fn test_timestamp_ttl_checks_on_immutable_binding() {
+        let timestamp = TimeStamp::from_datetime(DateTime::new(2023, 5, 28, 14, 36, 46));
+        assert!(timestamp.has_ttl_elapsed_since_update(Time::from_days(1)));
+        assert!(timestamp.has_ttl_elapsed_since_created(Time::from_days(1)));
+        let timestamp = TimeStamp::now();
+        assert!(!timestamp.has_ttl_elapsed_since_update(Time::from_hours(1)));
+        assert!(!timestamp.has_ttl_elapsed_since_created(Time::from_hours(1)));
+        assert!(timestamp.has_ttl_elapsed_since_update(Time::from_milliseconds(0)));
+        assert!(!timestamp.has_ttl_elapsed_since_update(Time::from_milliseconds(u64::MAX)));
+    }
+
+    #[test]
+    fn test_timestamp_typed_accessors() {
+        let created = DateTime::new(2023, 5, 1, 0, 0, 0);
+        let mut timestamp = TimeStamp::from_datetime(created.clone());
+        timestamp.updated = DateTime::new(2023, 5, 28, 14, 36, 46).to_milliseconds();
+        assert_eq!(created, timestamp.created_datetime());
+        assert_eq!(
+            DateTime::from_milliseconds(timestamp.created),
+            timestamp.created_datetime()
+        );
+        assert_eq!(
+            DateTime::from_milliseconds(timestamp.updated),
+            timestamp.updated_datetime()
+        );
+        assert_eq!(
+            timestamp.get_created(),
+            timestamp.created_datetime().format()
+        );
+        assert_eq!(
+            timestamp.get_updated(),
+            timestamp.updated_datetime().format()
+        );
+    }
+
+    #[test]
+    fn test_timestamp_ord_breaks_ties_by_created() {
+        let stamp = |created: u8, updated: u8| TimeStamp {
+            created: DateTime::new(2023, 5, created, 0, 0, 0).to_milliseconds(),
+            updated: DateTime::new(2023, 5, updated, 0, 0, 0).to_milliseconds(),
+        };
+        let mut stamps = vec![stamp(3, 20), stamp(1, 28), stamp(2, 20), stamp(1, 20)];
+        stamps.sort();
+        assert_eq!(
+            vec![stamp(1, 20), stamp(2, 20), stamp(3, 20), stamp(1, 28)],
+            stamps
+        );
+        assert_eq!(Ordering::Equal, stamp(1, 20).cmp(&stamp(1, 20)));
+        assert!(stamp(2, 20) > stamp(1, 20));
+    }
+
+    #[test]
+    fn test_timestamp_from_unix_epoch_milliseconds() {
+        let timestamp =
+            TimeStamp::from_unix_epoch_milliseconds(1_685_284_606_076, 1_685_284_700_000);
+        assert_eq!("2023-05-28 14:36:46.076", timestamp.get_created());
+        assert_eq!(
+            DateTime::from_unix_epoch_milliseconds(1_685_284_700_000),
+            timestamp.updated_datetime()
+        );
+        let legacy = now_milliseconds();
+        let timestamp = TimeStamp::from_unix_epoch_milliseconds(legacy, legacy);
+        assert!(!timestamp.has_ttl_elapsed_since_update(Time::from_hours(1)));
+        assert!(!timestamp.has_ttl_elapsed_since_created(Time::from_hours(1)));
+    }
+
+    #[test]
+    fn test_timestamp_ttl_fraction_extreme_updated() {
+        let now = DateTime::new(2023, 5, 28, 12, 0, 0);
+        let mut timestamp = TimeStamp::from_datetime(now.clone());
+        let ttl = Time::from_minutes(10);
+        timestamp.updated = i64::MIN;
+        assert_eq!(1.0, timestamp.ttl_fraction(ttl.clone(), now.clone()));
+        timestamp.updated = i64::MAX;
+        assert_eq!(0.0, timestamp.ttl_fraction(ttl, now));
+    }
 }
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// A fixed offset from UTC, stored in minutes, where positive offsets are east of UTC (e.g. `+02:00`).
+///
+/// `DateTime` values are always UTC. A `TimeZoneOffset` is used to explicitly convert local wall-clock times to and from UTC.
+///
+/// Expected usage is to use one of the following methods to create a new TimeZoneOffset struct:
+/// - `TimeZoneOffset::utc()`
+/// - `TimeZoneOffset::from_hours(hours)`
+/// - `TimeZoneOffset::from_minutes(minutes)`
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// assert_eq!(TimeZoneOffset::utc().format(), "+00:00");
+/// assert_eq!(TimeZoneOffset::from_hours(2).format(), "+02:00");
+/// assert_eq!(TimeZoneOffset::from_minutes(-570).format(), "-09:30");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct TimeZoneOffset {
+    minutes: i16,
+}
+
+impl TimeZoneOffset {
+    /// Create an offset of zero, which is UTC.
+    pub fn utc() -> Self {
+        Self { minutes: 0 }
+    }
+
+    /// Create an offset of whole hours from UTC, which is negative for offsets west of UTC.
+    pub fn from_hours(hours: i8) -> Self {
+        Self {
+            minutes: hours as i16 * 60,
+        }
+    }
+
+    /// Create an offset of minutes from UTC, which is negative for offsets west of UTC.
+    pub fn from_minutes(minutes: i16) -> Self {
+        Self { minutes }
+    }
+
+    /// Returns the total offset in minutes.
+    pub fn to_minutes(&self) -> i16 {
+        self.minutes
+    }
+
+    /// Returns the total offset in milliseconds.
+    pub fn to_milliseconds(&self) -> i64 {
+        self.minutes as i64 * 60 * 1000
+    }
+
+    /// Formats the offset as `+HH:MM` or `-HH:MM`.
+    pub fn format(&self) -> String {
+        let sign = if self.minutes < 0 { '-' } else { '+' };
+        let minutes = self.minutes.unsigned_abs();
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
@@ -0,0 +1,83 @@
+//! Serde helpers for storing a `DateTime` as an integer number of seconds since the Unix epoch.
+//!
+//! Serializing drops any milliseconds (rounding down), so values only round-trip to the second.
+//!
+//! Example:
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use stoicdreams_timestamp::prelude::*;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Token {
+//!     #[serde(with = "stoicdreams_timestamp::unix_seconds")]
+//!     expires: DateTime,
+//! }
+//!
+//! let token = Token { expires: DateTime::from_unix_epoch_milliseconds(1_685_284_606_076) };
+//! assert_eq!(serde_json::to_string(&token).unwrap(), r#"{"expires":1685284606}"#);
+//! ```
+use crate::prelude::*;
+use serde::de::{self, Deserialize};
+use serde::{Deserializer, Serializer};
+
+pub fn serialize<S>(value: &DateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_i64(value.to_unix_epoch_milliseconds().div_euclid(1000))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let seconds = i64::deserialize(deserializer)?;
+    seconds
+        .checked_mul(1000)
+        .and_then(|milliseconds| milliseconds.checked_add(EPOCH_START))
+        .filter(|milliseconds| *milliseconds >= 0)
+        .map(DateTime::from_milliseconds)
+        .ok_or_else(|| de::Error::custom(format!("Unix seconds {} are out of range", seconds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Token {
+        #[serde(with = "crate::unix_seconds")]
+        expires: DateTime,
+    }
+
+    #[test]
+    fn test_unix_seconds_serializes_integer() {
+        let token = Token {
+            expires: DateTime::from_unix_epoch_milliseconds(1_685_284_606_076),
+        };
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(r#"{"expires":1685284606}"#, json);
+    }
+
+    #[test]
+    fn test_unix_seconds_round_trip_to_second_precision() {
+        let expires = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let json = serde_json::to_string(&Token {
+            expires: expires.clone(),
+        })
+        .unwrap();
+        let token: Token = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            1_685_284_606_000,
+            token.expires.to_unix_epoch_milliseconds()
+        );
+        assert_eq!(expires.to_seconds(), token.expires.to_seconds());
+    }
+
+    #[test]
+    fn test_unix_seconds_rejects_before_ad_zero() {
+        let result = serde_json::from_str::<Token>(r#"{"expires":-99999999999}"#);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds since the Unix epoch (Midnight of Jan 1st, 1970), the same as Javascript's `Date.now()`.
+///
+/// Wrapping the value keeps it from being mixed up with `AdMillis`, which `DateTime` stores internally.
+/// The two types never convert implicitly, so use `to_ad_millis()` or `AdMillis::to_unix_millis()` to change base.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let unix_millis = UnixMillis(1_685_284_606_076);
+/// let datetime = DateTime::from_unix_millis(unix_millis).unwrap();
+/// assert_eq!(datetime.format(), "2023-05-28 14:36:46.076");
+/// assert_eq!(datetime.to_unix_millis(), unix_millis);
+/// ```
+///
+/// A `UnixMillis` cannot be passed where an `AdMillis` is expected:
+/// ```compile_fail
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let ad_millis: AdMillis = UnixMillis(0);
+/// ```
+///
+/// Serde reads and writes the bare integer, so wrapping a field in `UnixMillis` keeps its wire format unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct UnixMillis(pub DateTimeUnit);
+
+impl UnixMillis {
+    /// Converts to milliseconds since the start of AD 0, saturating at `i64::MAX`.
+    pub fn to_ad_millis(self) -> AdMillis {
+        AdMillis(self.0.saturating_add(EPOCH_START))
+    }
+}
+
+impl From<DateTime> for UnixMillis {
+    fn from(datetime: DateTime) -> Self {
+        datetime.to_unix_millis()
+    }
+}
+
+/// Returns a `TimeError` when the value is before AD 0 or after `DateTime::MAX`, the same as `DateTime::from_unix_millis()`.
+impl TryFrom<UnixMillis> for DateTime {
+    type Error = TimeError;
+
+    fn try_from(milliseconds: UnixMillis) -> Result<Self, Self::Error> {
+        DateTime::from_unix_millis(milliseconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unix_and_ad_millis_conversions() {
+        let unix_millis = UnixMillis(1_685_284_606_076);
+        let ad_millis = unix_millis.to_ad_millis();
+        assert_eq!(AdMillis(1_685_284_606_076 + EPOCH_START), ad_millis);
+        assert_eq!(unix_millis, ad_millis.to_unix_millis());
+        let datetime = DateTime::try_from(unix_millis).unwrap();
+        assert_eq!(datetime, DateTime::try_from(ad_millis).unwrap());
+        assert_eq!(unix_millis, UnixMillis::from(datetime.clone()));
+        assert_eq!(ad_millis, AdMillis::from(datetime));
+        assert_eq!(AdMillis(0), UnixMillis(-EPOCH_START).to_ad_millis());
+        assert!(DateTime::try_from(UnixMillis(-EPOCH_START - 1)).is_err());
+        assert!(DateTime::try_from(AdMillis(-1)).is_err());
+    }
+
+    #[test]
+    fn test_millis_newtypes_serialize_transparently() {
+        let json = serde_json::to_string(&UnixMillis(1_685_284_606_076)).unwrap();
+        assert_eq!("1685284606076", json);
+        assert_eq!(
+            UnixMillis(1_685_284_606_076),
+            serde_json::from_str::<UnixMillis>(&json).unwrap()
+        );
+        assert_eq!("-1", serde_json::to_string(&UnixMillis(-1)).unwrap());
+        assert!(serde_json::from_str::<UnixMillis>(r#"{"0":1}"#).is_err());
+        let ad_millis = UnixMillis(1_685_284_606_076).to_ad_millis();
+        let json = serde_json::to_string(&ad_millis).unwrap();
+        assert_eq!("63852417406076", json);
+        assert_eq!(ad_millis, serde_json::from_str::<AdMillis>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_unix_and_ad_millis_overflow() {
+        assert!(DateTime::try_from(UnixMillis(i64::MAX)).is_err());
+        assert!(DateTime::from_unix_millis(UnixMillis(i64::MIN)).is_err());
+        assert!(DateTime::try_from(AdMillis(i64::MAX)).is_err());
+        assert_eq!(AdMillis(i64::MAX), UnixMillis(i64::MAX).to_ad_millis());
+        assert_eq!(UnixMillis(i64::MIN), AdMillis(i64::MIN).to_unix_millis());
+        let max = DateTime::MAX;
+        assert_eq!(
+            Ok(max.clone()),
+            DateTime::from_unix_millis(max.to_unix_millis())
+        );
+        assert_eq!(
+            Ok(max.clone()),
+            DateTime::from_ad_millis(max.to_ad_millis())
+        );
+        assert!(DateTime::from_ad_millis(AdMillis(max.to_milliseconds() + 1)).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A stopwatch anchored to a wall-clock `DateTime` - Measured in milliseconds.
+///
+/// Unlike `StopWatch`, which measures precise nanosecond timings within a running process, a `WallStopwatch`
+/// only remembers the `DateTime` it was anchored to, so it can be stored and restored across process restarts.
+///
+/// Elapsed time is measured against the system (wall) clock, which is not monotonic. If the system clock is
+/// adjusted backwards to before the anchor then the elapsed time is reported as zero.
+///
+/// Expected usage is to use one of the following methods to create a new WallStopwatch struct:
+/// - `DateTime::stopwatch()`
+/// - `WallStopwatch::new(anchor)`
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let anchor = DateTime::new(2023, 5, 28, 14, 36, 46);
+/// let stopwatch = anchor.stopwatch();
+/// let now = DateTime::new(2023, 5, 28, 15, 36, 46);
+/// assert_eq!(stopwatch.elapsed_at(&now).to_hours(), 1);
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WallStopwatch {
+    anchor: DateTime,
+}
+
+impl WallStopwatch {
+    pub fn new(anchor: DateTime) -> Self {
+        Self { anchor }
+    }
+
+    /// Returns the `DateTime` this stopwatch measures from.
+    pub fn get_anchor(&self) -> &DateTime {
+        &self.anchor
+    }
+
+    /// Returns the wall-clock time that has passed since the anchor.
+    pub fn elapsed(&self) -> Time {
+        self.elapsed_at(&DateTime::now())
+    }
+
+    /// Returns the wall-clock time that has passed between the anchor and the provided `now`.
+    /// Returns zero when `now` is before the anchor.
+    pub fn elapsed_at(&self, now: &DateTime) -> Time {
+        let milliseconds = now.to_milliseconds() - self.anchor.to_milliseconds();
+        Time::from_milliseconds(milliseconds.max(0) as TimeUnit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wall_stopwatch_elapsed_from_injected_clock() {
+        let stopwatch = DateTime::new(2023, 5, 28, 14, 36, 46).stopwatch();
+        let now = DateTime::from_milliseconds(stopwatch.get_anchor().to_milliseconds() + 90_500);
+        let elapsed = stopwatch.elapsed_at(&now);
+        assert_eq!(90_500, elapsed.to_milliseconds());
+        assert_eq!("00:01:30.500", elapsed.format());
+    }
+
+    #[test]
+    fn test_wall_stopwatch_clock_before_anchor_is_zero() {
+        let stopwatch = DateTime::new(2023, 5, 28, 14, 36, 46).stopwatch();
+        let now = DateTime::new(2023, 5, 28, 14, 36, 45);
+        assert_eq!(0, stopwatch.elapsed_at(&now).to_milliseconds());
+    }
+
+    #[test]
+    fn test_wall_stopwatch_from_now() {
+        let stopwatch = DateTime::now().stopwatch();
+        assert_eq!(0, stopwatch.elapsed().to_minutes());
+    }
+}
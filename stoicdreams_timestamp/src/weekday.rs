@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const WEEKDAY_SHORT_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sunday,
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+];
+
+/// A day of the week, numbered the same as `DateTime::get_day_of_week()` where 0 is Sunday and 6 is Saturday.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let weekday = DateTime::new(2023, 5, 28, 14, 36, 46).weekday();
+/// assert_eq!(weekday, Weekday::Sunday);
+/// assert_eq!(weekday.name(), "Sunday");
+/// assert_eq!(weekday.short_name(), "Sun");
+/// assert_eq!(Weekday::from_name("mon"), Some(Weekday::Monday));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    /// Returns the weekday for a day of the week number, where 0 is Sunday and 6 is Saturday.
+    pub fn from_day_of_week(day_of_week: u8) -> Option<Self> {
+        WEEKDAYS.get(day_of_week as usize).copied()
+    }
+
+    /// Returns the weekday matching a full (`"Monday"`) or short (`"Mon"`) English name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..7)
+            .find(|&index| {
+                WEEKDAY_NAMES[index].eq_ignore_ascii_case(name)
+                    || WEEKDAY_SHORT_NAMES[index].eq_ignore_ascii_case(name)
+            })
+            .map(|index| WEEKDAYS[index])
+    }
+
+    /// Returns the day of the week number, where 0 is Sunday and 6 is Saturday.
+    pub fn to_day_of_week(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the full English name (e.g. `"Sunday"`).
+    pub fn name(&self) -> &'static str {
+        WEEKDAY_NAMES[*self as usize]
+    }
+
+    /// Returns the three letter English abbreviation (e.g. `"Sun"`).
+    pub fn short_name(&self) -> &'static str {
+        WEEKDAY_SHORT_NAMES[*self as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weekday_names_round_trip() {
+        for day_of_week in 0..7 {
+            let weekday = Weekday::from_day_of_week(day_of_week).unwrap();
+            assert_eq!(day_of_week, weekday.to_day_of_week());
+            assert_eq!(Some(weekday), Weekday::from_name(weekday.name()));
+            assert_eq!(Some(weekday), Weekday::from_name(weekday.short_name()));
+        }
+        assert_eq!(None, Weekday::from_day_of_week(7));
+        assert_eq!(None, Weekday::from_name("Funday"));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A `DateTime` instant paired with a `TimeZoneOffset` used for display.
+///
+/// The stored instant never changes, while all accessors and formatting show the wall-clock time at the offset.
+/// This allows switching the displayed timezone without modifying the stored `DateTime`.
+///
+/// Expected usage is to use `DateTime::in_timezone(offset)` to create a new ZonedDateTime struct.
+///
+/// Example:
+/// ```
+/// use stoicdreams_timestamp::prelude::*;
+///
+/// let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+/// let pacific = datetime.in_timezone(TimeZoneOffset::from_hours(-8));
+/// assert_eq!(pacific.get_hour_of_day(), 6);
+/// assert_eq!(pacific.format(), "2023-05-28 06:36:46.076-08:00");
+/// assert_eq!(pacific.to_datetime(), &datetime);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ZonedDateTime {
+    datetime: DateTime,
+    offset: TimeZoneOffset,
+}
+
+impl ZonedDateTime {
+    pub fn new(datetime: DateTime, offset: TimeZoneOffset) -> Self {
+        Self { datetime, offset }
+    }
+
+    /// Returns the UTC instant.
+    pub fn to_datetime(&self) -> &DateTime {
+        &self.datetime
+    }
+
+    /// Returns the offset used for display.
+    pub fn offset(&self) -> TimeZoneOffset {
+        self.offset
+    }
+
+    /// Returns the same instant displayed at a different offset.
+    pub fn with_offset(&self, offset: TimeZoneOffset) -> Self {
+        Self::new(self.datetime.clone(), offset)
+    }
+
+    /// Returns the wall-clock time at the offset.
+    fn local(&self) -> DateTime {
+        self.datetime.to_local(self.offset)
+    }
+
+    pub fn get_milliseconds_of_second(&self) -> u16 {
+        self.local().get_milliseconds_of_second()
+    }
+
+    pub fn get_seconds_of_minute(&self) -> u8 {
+        self.local().get_seconds_of_minute()
+    }
+
+    pub fn get_minutes_of_hour(&self) -> u8 {
+        self.local().get_minutes_of_hour()
+    }
+
+    pub fn get_hour_of_day(&self) -> u8 {
+        self.local().get_hour_of_day()
+    }
+
+    pub fn get_day_of_month(&self) -> u16 {
+        self.local().get_day_of_month()
+    }
+
+    pub fn get_month(&self) -> u8 {
+        self.local().get_month()
+    }
+
+    pub fn get_day_of_year(&self) -> u16 {
+        self.local().get_day_of_year()
+    }
+
+    /// Returns the day of the week, where 0 is Sunday and 6 is Saturday.
+    pub fn get_day_of_week(&self) -> u8 {
+        self.local().get_day_of_week()
+    }
+
+    pub fn get_year(&self) -> u16 {
+        self.local().get_year()
+    }
+
+    /// Formats the wall-clock time at the offset, followed by the offset.
+    /// e.g. `"2023-05-28 06:36:46.076-08:00"`
+    pub fn format(&self) -> String {
+        format!("{}{}", self.local().format(), self.offset.format())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoned_datetime_same_instant_different_hours() {
+        let datetime = DateTime::from_unix_epoch_milliseconds(1_685_284_606_076);
+        let utc = datetime.in_timezone(TimeZoneOffset::utc());
+        let pacific = datetime.in_timezone(TimeZoneOffset::from_hours(-8));
+        assert_eq!(14, utc.get_hour_of_day());
+        assert_eq!(6, pacific.get_hour_of_day());
+        assert_eq!(utc.to_datetime(), pacific.to_datetime());
+        assert_eq!("2023-05-28 14:36:46.076+00:00", utc.format());
+        assert_eq!("2023-05-28 06:36:46.076-08:00", pacific.format());
+        assert_eq!(TimeZoneOffset::from_hours(-8), pacific.offset());
+    }
+
+    #[test]
+    fn test_zoned_datetime_crosses_date() {
+        let datetime = DateTime::new(2023, 5, 28, 3, 0, 0);
+        let pacific = datetime.in_timezone(TimeZoneOffset::from_hours(-8));
+        assert_eq!(27, pacific.get_day_of_month());
+        assert_eq!(19, pacific.get_hour_of_day());
+        assert_eq!(6, pacific.get_day_of_week());
+        let tokyo = pacific.with_offset(TimeZoneOffset::from_hours(9));
+        assert_eq!(28, tokyo.get_day_of_month());
+        assert_eq!(12, tokyo.get_hour_of_day());
+    }
+
+    #[test]
+    fn test_zoned_datetime_near_ad_0_saturates() {
+        let pacific = DateTime::MIN.in_timezone(TimeZoneOffset::from_hours(-8));
+        assert_eq!("0000-01-01 00:00:00.000-08:00", pacific.format());
+        assert_eq!(&DateTime::MIN, pacific.to_datetime());
+        let tokyo = DateTime::MIN.in_timezone(TimeZoneOffset::from_hours(9));
+        assert_eq!("0000-01-01 09:00:00.000+09:00", tokyo.format());
+        assert_eq!(
+            DateTime::MIN,
+            DateTime::from_local(0, 1, 1, 3, 0, 0, TimeZoneOffset::from_hours(9))
+        );
+    }
+}